@@ -0,0 +1,874 @@
+use proc_macro2::{Ident, Span};
+use quote::{format_ident, ToTokens};
+use syn::{
+    custom_keyword, parenthesized,
+    parse::{Parse, ParseStream},
+    Attribute, Error, Expr, Fields, LitInt, LitStr, Path, Result, Token, Type,
+};
+
+custom_keyword!(code);
+custom_keyword!(code_with);
+custom_keyword!(forward);
+custom_keyword!(delegate);
+custom_keyword!(challenge);
+custom_keyword!(content_type);
+custom_keyword!(retry_after);
+custom_keyword!(ratelimit_limit);
+custom_keyword!(ratelimit_remaining);
+custom_keyword!(ratelimit_reset);
+custom_keyword!(location);
+custom_keyword!(body);
+custom_keyword!(expose);
+custom_keyword!(sensitive);
+custom_keyword!(errors);
+custom_keyword!(path);
+custom_keyword!(validation);
+custom_keyword!(example);
+custom_keyword!(formatter);
+custom_keyword!(grpc);
+custom_keyword!(error_code);
+custom_keyword!(log);
+custom_keyword!(message);
+custom_keyword!(auto);
+
+#[derive(Default)]
+pub struct TypeAttrs {
+    pub code: Option<CodeArg>,
+    pub code_with: Option<CodeWithArg>,
+    pub forward: Option<ForwardArg>,
+    pub challenge: Option<ChallengeArg>,
+    pub example: Option<ExampleArg>,
+    pub formatter: Option<FormatterArg>,
+    pub content_type: Option<ContentTypeArg>,
+    pub grpc: Option<GrpcArg>,
+    pub error_code: Option<ErrorCodeArg>,
+    pub log: Option<LogArg>,
+    pub message: Option<MessageArg>,
+    /// The `Display` message, when thiserror's `error` attribute gives it as a plain literal.
+    pub display: Option<String>,
+    /// Whether the status is the most severe one among the aggregated errors, with `code` as
+    /// the fallback when there are none.
+    pub status_from_errors: bool,
+}
+
+impl TypeAttrs {
+    pub fn new(input: &[Attribute]) -> Result<Self> {
+        let mut ret = Self::default();
+        for attr in input.iter().filter(|a| a.path.is_ident("response")) {
+            ret.parse_attrs(attr)?;
+        }
+        ret.display = input
+            .iter()
+            .find(|a| a.path.is_ident("error"))
+            .and_then(|a| a.parse_args::<LitStr>().ok())
+            .and_then(|lit| literal_message(&lit.value()));
+        Ok(ret)
+    }
+
+    fn parse_attrs(&mut self, attr: &Attribute) -> Result<()> {
+        attr.parse_args_with(|input: ParseStream| {
+            loop {
+                let lookahead = input.lookahead1();
+                if lookahead.peek(code) {
+                    if self.code.is_some() {
+                        return Err(Error::new_spanned(
+                            input.parse::<code>().unwrap(),
+                            "duplicate code argument",
+                        ));
+                    }
+                    self.code = Some(input.parse()?);
+                } else if lookahead.peek(code_with) {
+                    if self.code_with.is_some() {
+                        return Err(Error::new_spanned(
+                            input.parse::<code_with>().unwrap(),
+                            "duplicate code_with argument",
+                        ));
+                    }
+                    self.code_with = Some(input.parse()?);
+                } else if lookahead.peek(forward) {
+                    if self.forward.is_some() {
+                        return Err(Error::new_spanned(
+                            input.parse::<forward>().unwrap(),
+                            "duplicate forward argument",
+                        ));
+                    }
+                    self.forward = Some(input.parse()?);
+                } else if lookahead.peek(challenge) {
+                    if self.challenge.is_some() {
+                        return Err(Error::new_spanned(
+                            input.parse::<challenge>().unwrap(),
+                            "duplicate challenge argument",
+                        ));
+                    }
+                    self.challenge = Some(input.parse()?);
+                } else if lookahead.peek(example) {
+                    if self.example.is_some() {
+                        return Err(Error::new_spanned(
+                            input.parse::<example>().unwrap(),
+                            "duplicate example argument",
+                        ));
+                    }
+                    self.example = Some(input.parse()?);
+                } else if lookahead.peek(formatter) {
+                    if self.formatter.is_some() {
+                        return Err(Error::new_spanned(
+                            input.parse::<formatter>().unwrap(),
+                            "duplicate formatter argument",
+                        ));
+                    }
+                    self.formatter = Some(input.parse()?);
+                } else if lookahead.peek(content_type) {
+                    if self.content_type.is_some() {
+                        return Err(Error::new_spanned(
+                            input.parse::<content_type>().unwrap(),
+                            "duplicate content_type argument",
+                        ));
+                    }
+                    self.content_type = Some(input.parse()?);
+                } else if lookahead.peek(grpc) {
+                    if self.grpc.is_some() {
+                        return Err(Error::new_spanned(
+                            input.parse::<grpc>().unwrap(),
+                            "duplicate grpc argument",
+                        ));
+                    }
+                    self.grpc = Some(input.parse()?);
+                } else if lookahead.peek(error_code) {
+                    if self.error_code.is_some() {
+                        return Err(Error::new_spanned(
+                            input.parse::<error_code>().unwrap(),
+                            "duplicate error_code argument",
+                        ));
+                    }
+                    self.error_code = Some(input.parse()?);
+                } else if lookahead.peek(log) {
+                    if self.log.is_some() {
+                        return Err(Error::new_spanned(
+                            input.parse::<log>().unwrap(),
+                            "duplicate log argument",
+                        ));
+                    }
+                    self.log = Some(input.parse()?);
+                } else if lookahead.peek(message) {
+                    if self.message.is_some() {
+                        return Err(Error::new_spanned(
+                            input.parse::<message>().unwrap(),
+                            "duplicate message argument",
+                        ));
+                    }
+                    self.message = Some(input.parse()?);
+                } else {
+                    return Err(lookahead.error());
+                }
+                if input.parse::<Option<Token![,]>>()?.is_none() {
+                    break;
+                }
+            }
+            Ok(())
+        })
+    }
+}
+
+#[derive(Clone)]
+pub struct CodeArg {
+    pub kw: code,
+    pub eq_token: Token![=],
+    pub code: u16,
+}
+
+impl Parse for CodeArg {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let kw = input.parse()?;
+        let eq_token = input.parse()?;
+        let code_lit: LitInt = input.parse()?;
+        let code = code_lit.base10_parse()?;
+        if !STATUS_CODES.contains(&code) {
+            return Err(Error::new_spanned(code_lit, "invalid status code"));
+        }
+        Ok(Self { kw, eq_token, code })
+    }
+}
+
+impl CodeArg {
+    /// The code of a variant that doesn't specify one, such as 422 for aggregated errors.
+    pub fn implicit(value: u16, span: Span) -> Self {
+        Self {
+            kw: code(span),
+            eq_token: Token![=](span),
+            code: value,
+        }
+    }
+}
+
+/// The status of the catcher that responds in place of the annotated type or variant.
+#[derive(Clone)]
+pub struct ForwardArg {
+    pub kw: forward,
+    pub code: u16,
+}
+
+impl Parse for ForwardArg {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let kw = input.parse()?;
+        input.parse::<Token![=]>()?;
+        let code_lit: LitInt = input.parse()?;
+        let code = code_lit.base10_parse()?;
+        if !STATUS_CODES.contains(&code) {
+            return Err(Error::new_spanned(code_lit, "invalid status code"));
+        }
+        if !(400..=599).contains(&code) {
+            return Err(Error::new_spanned(
+                code_lit,
+                "forward requires a 4xx or 5xx code",
+            ));
+        }
+        Ok(Self { kw, code })
+    }
+}
+
+/// Status codes accepted by `rocket::http::Status::from_code`.
+///
+/// Kept in sync with Rocket by hand so that this crate doesn't need to depend on it.
+const STATUS_CODES: &[u16] = &[
+    100, 101, 102, // informational
+    200, 201, 202, 203, 204, 205, 206, 207, 208, 226, // success
+    300, 301, 302, 303, 304, 305, 307, 308, // redirection
+    400, 401, 402, 403, 404, 405, 406, 407, 408, 409, 410, 411, 412, 413, 414, 415, 416, 417, 418,
+    421, 422, 423, 424, 426, 428, 429, 431, 451, // client error
+    500, 501, 502, 503, 504, 505, 506, 507, 508, 510, 511, // server error
+];
+
+/// A function resolving the status at runtime, with `code` as the statically known fallback.
+#[derive(Clone)]
+pub struct CodeWithArg {
+    pub kw: code_with,
+    pub expr: Expr,
+}
+
+impl Parse for CodeWithArg {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let kw = input.parse()?;
+        input.parse::<Token![=]>()?;
+        let expr = input.parse()?;
+        Ok(Self { kw, expr })
+    }
+}
+
+/// The type implementing `ErrorFormatter` used to build the body.
+#[derive(Clone)]
+pub struct FormatterArg {
+    pub kw: formatter,
+    pub path: Path,
+}
+
+impl Parse for FormatterArg {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let kw = input.parse()?;
+        input.parse::<Token![=]>()?;
+        let path = input.parse()?;
+        Ok(Self { kw, path })
+    }
+}
+
+/// The media type replacing the content type of the body.
+#[derive(Clone)]
+pub struct ContentTypeArg {
+    pub kw: content_type,
+    pub media_type: MediaType,
+}
+
+impl Parse for ContentTypeArg {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let kw = input.parse()?;
+        input.parse::<Token![=]>()?;
+        let value: LitStr = input.parse()?;
+        let media_type = parse_media_type(&value.value())
+            .ok_or_else(|| Error::new_spanned(&value, "invalid content type"))?;
+        Ok(Self { kw, media_type })
+    }
+}
+
+/// A media type parsed at compile time, so that it doesn't need to be parsed at runtime.
+#[derive(Clone)]
+pub struct MediaType {
+    pub top: String,
+    pub sub: String,
+    /// The parameters, with quoted values unquoted.
+    pub params: Vec<(String, String)>,
+}
+
+/// Splits a `media-type` as defined in RFC 7231, section 3.1.1.1, into its type, subtype and
+/// parameters.
+///
+/// Rocket writes parameter values as they are, so the values that are still not tokens once
+/// unquoted are rejected.
+fn parse_media_type(s: &str) -> Option<MediaType> {
+    let end = s.find(';').unwrap_or(s.len());
+    let mut media_type = s[..end].trim_end().splitn(2, '/');
+    let top = media_type.next().filter(|t| is_token(t))?;
+    let sub = media_type.next().filter(|t| is_token(t))?;
+    let mut params = Vec::new();
+    let mut rest = &s[end..];
+    while let Some(param) = rest.strip_prefix(';') {
+        let param = param.trim_start_matches(&[' ', '\t'][..]);
+        let i = param.find('=')?;
+        let (name, value) = (&param[..i], &param[i + 1..]);
+        let value_len = if value.starts_with('"') {
+            quoted_string_len(value)?
+        } else {
+            value.find(';').unwrap_or(value.len())
+        };
+        let (value, next) = value.split_at(value_len);
+        let value = value.trim_end_matches(&[' ', '\t'][..]);
+        let value = match value.strip_prefix('"') {
+            Some(quoted) => unescape(&quoted[..quoted.len() - 1]),
+            None => value.to_owned(),
+        };
+        if !is_token(name) || !is_token(&value) {
+            return None;
+        }
+        params.push((name.to_owned(), value));
+        rest = next.trim_start_matches(&[' ', '\t'][..]);
+    }
+    if !rest.is_empty() {
+        return None;
+    }
+    Some(MediaType {
+        top: top.to_owned(),
+        sub: sub.to_owned(),
+        params,
+    })
+}
+
+/// The length of the `quoted-string` (RFC 7230, section 3.2.6) at the start of `s`.
+fn quoted_string_len(s: &str) -> Option<usize> {
+    let mut escaped = false;
+    for (i, c) in s.char_indices().skip(1) {
+        if c.is_control() && c != '\t' {
+            return None;
+        }
+        match (escaped, c) {
+            (false, '\\') => escaped = true,
+            (false, '"') => return Some(i + 1),
+            _ => escaped = false,
+        }
+    }
+    None
+}
+
+/// The content of a `quoted-string` without its quotes, with its `quoted-pair`s unescaped.
+fn unescape(s: &str) -> String {
+    let mut ret = String::with_capacity(s.len());
+    let mut chars = s.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => ret.extend(chars.next()),
+            c => ret.push(c),
+        }
+    }
+    ret
+}
+
+/// A machine-readable error code, sent in the `X-Error-Code` header and in structured bodies.
+#[derive(Clone)]
+pub struct ErrorCodeArg {
+    pub kw: error_code,
+    /// `None` for `auto`, which derives the code from the type and variant names.
+    pub code: Option<LitStr>,
+}
+
+impl Parse for ErrorCodeArg {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let kw = input.parse()?;
+        input.parse::<Token![=]>()?;
+        if input.parse::<Option<auto>>()?.is_some() {
+            return Ok(Self { kw, code: None });
+        }
+        let code: LitStr = input.parse()?;
+        if !is_token(&code.value()) {
+            return Err(Error::new_spanned(code, "invalid error code"));
+        }
+        Ok(Self {
+            kw,
+            code: Some(code),
+        })
+    }
+}
+
+impl ErrorCodeArg {
+    /// Replaces `auto` with the SCREAMING_SNAKE_CASE concatenation of `idents`.
+    pub fn resolve(&mut self, idents: &[&Ident]) {
+        if self.code.is_none() {
+            let code = idents
+                .iter()
+                .map(|ident| screaming_snake_case(&ident.to_string()))
+                .collect::<Vec<_>>()
+                .join("_");
+            let span = idents.last().map_or(self.kw.span, |ident| ident.span());
+            self.code = Some(LitStr::new(&code, span));
+        }
+    }
+}
+
+fn screaming_snake_case(s: &str) -> String {
+    let s = s.trim_start_matches("r#");
+    let chars: Vec<char> = s.chars().collect();
+    let mut ret = String::new();
+    for (i, c) in chars.iter().enumerate() {
+        if i > 0 && c.is_uppercase() {
+            let prev = chars[i - 1];
+            let next_lower = matches!(chars.get(i + 1), Some(n) if n.is_lowercase());
+            if prev.is_lowercase() || prev.is_ascii_digit() || (prev.is_uppercase() && next_lower) {
+                ret.push('_');
+            }
+        }
+        ret.extend(c.to_uppercase());
+    }
+    ret
+}
+
+/// The level at which responses are logged with the `logging` feature, as a variant of the
+/// runtime `Level`, or `None` for `off`.
+#[derive(Clone)]
+pub struct LogArg {
+    #[cfg_attr(not(feature = "logging"), allow(dead_code))]
+    pub level: Option<Ident>,
+}
+
+impl Parse for LogArg {
+    fn parse(input: ParseStream) -> Result<Self> {
+        input.parse::<log>()?;
+        input.parse::<Token![=]>()?;
+        let level_lit: LitStr = input.parse()?;
+        let level = match level_lit.value().as_str() {
+            "off" => None,
+            "debug" => Some(Ident::new("Debug", level_lit.span())),
+            "info" => Some(Ident::new("Info", level_lit.span())),
+            "warn" => Some(Ident::new("Warn", level_lit.span())),
+            "error" => Some(Ident::new("Error", level_lit.span())),
+            _ => {
+                return Err(Error::new_spanned(
+                    level_lit,
+                    "log must be one of \"off\", \"debug\", \"info\", \"warn\" or \"error\"",
+                ))
+            }
+        };
+        Ok(Self { level })
+    }
+}
+
+/// A static message replacing the `Display` message in responses.
+pub struct MessageArg {
+    pub kw: message,
+    pub value: LitStr,
+}
+
+impl Parse for MessageArg {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let kw = input.parse()?;
+        input.parse::<Token![=]>()?;
+        let value = input.parse()?;
+        Ok(Self { kw, value })
+    }
+}
+
+/// Unescapes the format string `s`, or returns `None` if it has arguments to format.
+fn literal_message(s: &str) -> Option<String> {
+    let mut ret = String::with_capacity(s.len());
+    let mut chars = s.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            // `{{` and `}}` stand for a single brace
+            '{' | '}' if chars.next() != Some(c) => return None,
+            c => ret.push(c),
+        }
+    }
+    Some(ret)
+}
+
+/// The `tonic::Code` variant used when converting to a gRPC status.
+#[derive(Clone)]
+pub struct GrpcArg {
+    #[cfg_attr(not(feature = "tonic"), allow(dead_code))]
+    pub code: Ident,
+}
+
+impl Parse for GrpcArg {
+    fn parse(input: ParseStream) -> Result<Self> {
+        input.parse::<grpc>()?;
+        input.parse::<Token![=]>()?;
+        let code_lit: LitStr = input.parse()?;
+        if !GRPC_CODES.contains(&code_lit.value().as_str()) {
+            return Err(Error::new_spanned(code_lit, "invalid grpc code"));
+        }
+        if code_lit.value() == "Ok" {
+            return Err(Error::new_spanned(
+                code_lit,
+                "an error can't be converted into the Ok grpc code",
+            ));
+        }
+        let code = Ident::new(&code_lit.value(), code_lit.span());
+        Ok(Self { code })
+    }
+}
+
+/// The variants of `tonic::Code`.
+const GRPC_CODES: &[&str] = &[
+    "Ok",
+    "Cancelled",
+    "Unknown",
+    "InvalidArgument",
+    "DeadlineExceeded",
+    "NotFound",
+    "AlreadyExists",
+    "PermissionDenied",
+    "ResourceExhausted",
+    "FailedPrecondition",
+    "Aborted",
+    "OutOfRange",
+    "Unimplemented",
+    "Internal",
+    "Unavailable",
+    "DataLoss",
+    "Unauthenticated",
+];
+
+/// The `tonic::Code` variant used for `code` when no `grpc` argument is given.
+///
+/// Statuses that aren't errors, such as redirects, still describe a failed call, so they are
+/// converted into `Unknown` rather than `Ok`.
+#[cfg(feature = "tonic")]
+pub fn default_grpc_code(code: u16) -> &'static str {
+    match code {
+        100..=399 => "Unknown",
+        400 | 422 => "InvalidArgument",
+        401 => "Unauthenticated",
+        403 => "PermissionDenied",
+        404 | 410 => "NotFound",
+        408 | 504 => "DeadlineExceeded",
+        409 => "Aborted",
+        416 => "OutOfRange",
+        429 => "ResourceExhausted",
+        400..=499 => "FailedPrecondition",
+        501 => "Unimplemented",
+        502 | 503 => "Unavailable",
+        _ => "Internal",
+    }
+}
+
+/// An expression constructing the annotated type or variant, used by the `testing` feature.
+pub struct ExampleArg {
+    pub kw: example,
+    #[cfg_attr(not(feature = "testing"), allow(dead_code))]
+    pub expr: Expr,
+}
+
+impl Parse for ExampleArg {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let kw = input.parse()?;
+        input.parse::<Token![=]>()?;
+        let expr = input.parse()?;
+        Ok(Self { kw, expr })
+    }
+}
+
+/// An authentication challenge, rendered into a `WWW-Authenticate` or `Proxy-Authenticate` header.
+#[derive(Clone)]
+pub struct ChallengeArg {
+    pub kw: challenge,
+    pub scheme: String,
+    pub params: Vec<(String, String)>,
+}
+
+impl Parse for ChallengeArg {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let kw = input.parse()?;
+        let content;
+        parenthesized!(content in input);
+        let mut scheme = None;
+        let mut params = Vec::<(String, String)>::new();
+        loop {
+            let name: Ident = content.parse()?;
+            content.parse::<Token![=]>()?;
+            let value: LitStr = content.parse()?;
+            if name == "scheme" {
+                if scheme.is_some() {
+                    return Err(Error::new_spanned(name, "duplicate scheme argument"));
+                }
+                if !is_token(&value.value()) {
+                    return Err(Error::new_spanned(value, "invalid authentication scheme"));
+                }
+                scheme = Some(value.value());
+            } else {
+                let name_str = name.to_string();
+                if params
+                    .iter()
+                    .any(|(n, _)| n.eq_ignore_ascii_case(&name_str))
+                {
+                    return Err(Error::new_spanned(name, "duplicate challenge parameter"));
+                }
+                if value.value().chars().any(char::is_control) {
+                    return Err(Error::new_spanned(
+                        value,
+                        "challenge parameter can't contain control characters",
+                    ));
+                }
+                params.push((name_str, value.value()));
+            }
+            if content.parse::<Option<Token![,]>>()?.is_none() {
+                break;
+            }
+        }
+        let scheme =
+            scheme.ok_or_else(|| Error::new_spanned(kw, "challenge scheme must be specified"))?;
+        Ok(Self { kw, scheme, params })
+    }
+}
+
+/// Whether `s` is a `token` as defined in RFC 7230, section 3.2.6.
+fn is_token(s: &str) -> bool {
+    !s.is_empty()
+        && s.chars()
+            .all(|c| c.is_ascii_alphanumeric() || "!#$%&'*+-.^_`|~".contains(c))
+}
+
+#[derive(Default)]
+pub struct FieldsAttrs {
+    pub delegate: Option<Delegate>,
+    pub challenge_params: Vec<ChallengeParam>,
+    pub retry_after: Option<FieldMarker<retry_after>>,
+    pub ratelimit_limit: Option<FieldMarker<ratelimit_limit>>,
+    pub ratelimit_remaining: Option<FieldMarker<ratelimit_remaining>>,
+    pub ratelimit_reset: Option<FieldMarker<ratelimit_reset>>,
+    pub location: Option<FieldMarker<location>>,
+    pub body: Option<FieldMarker<body>>,
+    pub exposed: Vec<NamedMarker<expose>>,
+    pub sensitive: Vec<Sensitive>,
+    pub errors: Option<FieldMarker<errors>>,
+    pub path: Option<FieldMarker<path>>,
+    pub validation: Option<FieldMarker<validation>>,
+}
+
+/// A field tagged with the keyword `K`.
+pub struct FieldMarker<K> {
+    pub kw: K,
+    pub ident: Ident,
+    pub ty: Type,
+}
+
+pub type Delegate = FieldMarker<delegate>;
+
+/// A field whose rendering is hidden from logs and exposed members.
+pub type Sensitive = FieldMarker<sensitive>;
+
+/// A field tagged with the keyword `K`, under the given name.
+pub struct NamedMarker<K> {
+    pub kw: K,
+    pub name: String,
+    pub ident: Ident,
+    pub ty: Type,
+}
+
+/// A challenge parameter whose value is taken from the `Display` of a field.
+pub type ChallengeParam = NamedMarker<challenge>;
+
+impl FieldsAttrs {
+    pub fn new(fields: &Fields) -> Result<Self> {
+        let mut ret = Self::default();
+        for (i, field) in fields.iter().enumerate() {
+            let ident = field
+                .ident
+                .clone()
+                .unwrap_or_else(|| format_ident!("_{}", i));
+            for attr in field.attrs.iter().filter(|a| a.path.is_ident("response")) {
+                ret.parse_attrs(attr, &ident, field.ident.is_some(), &field.ty)?;
+            }
+        }
+        Ok(ret)
+    }
+
+    /// The fields read when building a response from a code.
+    pub fn bindings(&self) -> Vec<&Ident> {
+        let mut ret: Vec<&Ident> = self.challenge_params.iter().map(|p| &p.ident).collect();
+        ret.extend(self.retry_after.as_ref().map(|m| &m.ident));
+        ret.extend(self.ratelimit_limit.as_ref().map(|m| &m.ident));
+        ret.extend(self.ratelimit_remaining.as_ref().map(|m| &m.ident));
+        ret.extend(self.ratelimit_reset.as_ref().map(|m| &m.ident));
+        ret.extend(self.location.as_ref().map(|m| &m.ident));
+        ret.extend(self.body.as_ref().map(|m| &m.ident));
+        ret.extend(self.exposed.iter().map(|m| &m.ident));
+        ret.extend(self.errors.as_ref().map(|m| &m.ident));
+        ret.extend(self.validation.as_ref().map(|m| &m.ident));
+        ret.dedup();
+        ret
+    }
+
+    /// The spans of all the field attributes other than `delegate`.
+    pub fn marker_spans(&self) -> Vec<Span> {
+        let mut ret = self.response_marker_spans();
+        ret.extend(self.sensitive.iter().map(|m| m.kw.span));
+        ret.extend(self.path.as_ref().map(|m| m.kw.span));
+        ret
+    }
+
+    /// The spans of the field attributes that end up in the response.
+    pub fn response_marker_spans(&self) -> Vec<Span> {
+        let mut ret: Vec<Span> = self.challenge_params.iter().map(|p| p.kw.span).collect();
+        ret.extend(self.retry_after.as_ref().map(|m| m.kw.span));
+        ret.extend(self.ratelimit_limit.as_ref().map(|m| m.kw.span));
+        ret.extend(self.ratelimit_remaining.as_ref().map(|m| m.kw.span));
+        ret.extend(self.ratelimit_reset.as_ref().map(|m| m.kw.span));
+        ret.extend(self.location.as_ref().map(|m| m.kw.span));
+        ret.extend(self.body.as_ref().map(|m| m.kw.span));
+        ret.extend(self.exposed.iter().map(|m| m.kw.span));
+        ret.extend(self.errors.as_ref().map(|m| m.kw.span));
+        ret.extend(self.validation.as_ref().map(|m| m.kw.span));
+        ret
+    }
+
+    fn parse_attrs(
+        &mut self,
+        attr: &Attribute,
+        ident: &Ident,
+        named: bool,
+        ty: &Type,
+    ) -> Result<()> {
+        attr.parse_args_with(|input: ParseStream| {
+            loop {
+                let lookahead = input.lookahead1();
+                if lookahead.peek(delegate) {
+                    set_marker(&mut self.delegate, input.parse()?, ident, ty)?;
+                } else if lookahead.peek(retry_after) {
+                    set_marker(&mut self.retry_after, input.parse()?, ident, ty)?;
+                } else if lookahead.peek(ratelimit_limit) {
+                    set_marker(&mut self.ratelimit_limit, input.parse()?, ident, ty)?;
+                } else if lookahead.peek(ratelimit_remaining) {
+                    set_marker(&mut self.ratelimit_remaining, input.parse()?, ident, ty)?;
+                } else if lookahead.peek(ratelimit_reset) {
+                    set_marker(&mut self.ratelimit_reset, input.parse()?, ident, ty)?;
+                } else if lookahead.peek(body) {
+                    set_marker(&mut self.body, input.parse()?, ident, ty)?;
+                } else if lookahead.peek(errors) {
+                    set_marker(&mut self.errors, input.parse()?, ident, ty)?;
+                } else if lookahead.peek(path) {
+                    set_marker(&mut self.path, input.parse()?, ident, ty)?;
+                } else if lookahead.peek(validation) {
+                    set_marker(&mut self.validation, input.parse()?, ident, ty)?;
+                } else if lookahead.peek(location) {
+                    set_marker(&mut self.location, input.parse()?, ident, ty)?;
+                } else if lookahead.peek(challenge) {
+                    let kw = input.parse::<challenge>()?;
+                    let name = parse_marker_name(input, &kw, ident, named)?;
+                    if !is_token(&name.value()) {
+                        return Err(Error::new_spanned(name, "invalid challenge parameter name"));
+                    }
+                    let name = name.value();
+                    if self
+                        .challenge_params
+                        .iter()
+                        .any(|p| p.name.eq_ignore_ascii_case(&name))
+                    {
+                        return Err(Error::new_spanned(kw, "duplicate challenge parameter"));
+                    }
+                    self.challenge_params.push(NamedMarker {
+                        kw,
+                        name,
+                        ident: ident.clone(),
+                        ty: ty.clone(),
+                    });
+                } else if lookahead.peek(sensitive) {
+                    let kw = input.parse::<sensitive>()?;
+                    if self.sensitive.iter().any(|m| m.ident == *ident) {
+                        return Err(Error::new_spanned(kw, "duplicate sensitive tag"));
+                    }
+                    self.sensitive.push(FieldMarker {
+                        kw,
+                        ident: ident.clone(),
+                        ty: ty.clone(),
+                    });
+                } else if lookahead.peek(expose) {
+                    let kw = input.parse::<expose>()?;
+                    let name_lit = parse_marker_name(input, &kw, ident, named)?;
+                    let name = name_lit.value();
+                    if FORMATTED_MEMBERS.contains(&name.as_str()) {
+                        return Err(Error::new_spanned(
+                            name_lit,
+                            format!(
+                                "can't expose a field as `{}`, which is written by the formatters",
+                                name
+                            ),
+                        ));
+                    }
+                    if self.exposed.iter().any(|m| m.name == name) {
+                        return Err(Error::new_spanned(
+                            name_lit,
+                            format!("duplicate exposed name `{}`", name),
+                        ));
+                    }
+                    self.exposed.push(NamedMarker {
+                        kw,
+                        name,
+                        ident: ident.clone(),
+                        ty: ty.clone(),
+                    });
+                } else {
+                    return Err(lookahead.error());
+                }
+                if input.parse::<Option<Token![,]>>()?.is_none() {
+                    break;
+                }
+            }
+            Ok(())
+        })
+    }
+}
+
+/// The members written by the `Json` and `ProblemJson` formatters, which exposed fields would
+/// replace or be replaced by.
+const FORMATTED_MEMBERS: &[&str] = &[
+    "type",
+    "title",
+    "status",
+    "detail",
+    "message",
+    "error_code",
+    "errors",
+];
+
+fn set_marker<K: ToTokens>(
+    marker: &mut Option<FieldMarker<K>>,
+    kw: K,
+    ident: &Ident,
+    ty: &Type,
+) -> Result<()> {
+    if marker.is_some() {
+        let msg = format!("duplicate {} tag", kw.to_token_stream());
+        return Err(Error::new_spanned(kw, msg));
+    }
+    *marker = Some(FieldMarker {
+        kw,
+        ident: ident.clone(),
+        ty: ty.clone(),
+    });
+    Ok(())
+}
+
+/// Parses the optional `= "name"` following `kw`, defaulting to the name of the field.
+fn parse_marker_name<K: ToTokens>(
+    input: ParseStream,
+    kw: &K,
+    ident: &Ident,
+    named: bool,
+) -> Result<LitStr> {
+    if input.parse::<Option<Token![=]>>()?.is_some() {
+        input.parse()
+    } else if named {
+        Ok(LitStr::new(&ident.to_string(), ident.span()))
+    } else {
+        let msg = format!(
+            "{} name must be specified for unnamed fields",
+            kw.to_token_stream()
+        );
+        Err(Error::new_spanned(kw, msg))
+    }
+}
@@ -0,0 +1,79 @@
+//! Measures the size of the expansion, which grows with the number of variants, and fails when
+//! it doesn't grow linearly.
+//!
+//! Run with `cargo test -p rocket_simple_responder_impl --release bench -- --nocapture`.
+
+use proc_macro2::{TokenStream, TokenTree};
+use quote::{format_ident, quote};
+
+use crate::expand;
+
+/// Counts the tokens of `stream`, including those nested in groups.
+fn count(stream: TokenStream) -> usize {
+    stream
+        .into_iter()
+        .map(|tt| match tt {
+            TokenTree::Group(group) => 1 + count(group.stream()),
+            _ => 1,
+        })
+        .sum()
+}
+
+/// The size of the expansion for an enum with `n` variants built by `variant`.
+fn expansion_size(n: usize, variant: fn(usize) -> TokenStream) -> usize {
+    let variants = (0..n).map(variant);
+    let input = quote! {
+        #[response(code = 500)]
+        enum Error {
+            #(#variants,)*
+        }
+    };
+    count(expand(syn::parse2(input).unwrap()))
+}
+
+fn plain(i: usize) -> TokenStream {
+    let ident = format_ident!("Variant{}", i);
+    quote! {
+        #[error("error")]
+        #[response(code = 404)]
+        #ident
+    }
+}
+
+fn with_headers(i: usize) -> TokenStream {
+    let ident = format_ident!("Variant{}", i);
+    let error_code = format!("ERROR_{}", i);
+    quote! {
+        #[error("error {0}")]
+        #[response(code = 429, error_code = #error_code)]
+        #ident(#[response(retry_after)] u64)
+    }
+}
+
+#[test]
+fn expansion_size_per_variant() {
+    for (name, variant) in [
+        ("plain", plain as fn(usize) -> TokenStream),
+        ("headers", with_headers),
+    ]
+    .iter()
+    {
+        let one = expansion_size(1, *variant);
+        let half = expansion_size(41, *variant);
+        let many = expansion_size(81, *variant);
+        println!(
+            "{}: {} tokens for 1 variant, {} for 81, {} per variant",
+            name,
+            one,
+            many,
+            (many - one) / 80
+        );
+        // every variant adds the same arms, whatever the number of variants
+        assert_eq!(
+            many - half,
+            half - one,
+            "{} variants don't expand linearly",
+            name
+        );
+    }
+}
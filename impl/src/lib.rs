@@ -0,0 +1,1139 @@
+use attrs::{ChallengeArg, ChallengeParam, FieldsAttrs, MediaType, Sensitive, TypeAttrs};
+use proc_macro::TokenStream;
+use proc_macro2::Ident;
+use quote::{format_ident, quote};
+use syn::{ext::IdentExt, parse_macro_input, parse_quote, Fields, WhereClause};
+use utils::{has_body, Item, ItemData};
+#[cfg(feature = "testing")]
+use utils::{ItemDataStruct, ItemDataUnion};
+
+mod attrs;
+mod utils;
+
+#[cfg(test)]
+mod bench;
+#[cfg(test)]
+mod tests;
+
+#[proc_macro_derive(SimpleResponder, attributes(response))]
+pub fn derive_maskable(input: TokenStream) -> TokenStream {
+    let input: Item = parse_macro_input!(input);
+    expand(input).into()
+}
+
+fn expand(input: Item) -> proc_macro2::TokenStream {
+    let mut impl_generics = input.generics.clone();
+    impl_generics.params.push(parse_quote!('_r));
+    impl_generics.params.push(parse_quote!('_o: '_r));
+    let (_, ty_generics, where_clauses) = input.generics.split_for_impl();
+    let mut expanded_where_clauses: WhereClause = parse_quote! { where };
+
+    if let Some(where_clauses) = where_clauses {
+        for predicate in &where_clauses.predicates {
+            expanded_where_clauses.predicates.push(predicate.clone());
+        }
+    }
+
+    let ident = input.ident;
+    let item_ident = &ident;
+
+    #[cfg(feature = "testing")]
+    let examples_impl = {
+        let (impl_generics, _, _) = input.generics.split_for_impl();
+        let examples = match &input.data {
+            ItemData::Enum(data) => data
+                .variants
+                .iter()
+                .filter_map(|v| {
+                    let example = &v.variant_attrs.example.as_ref()?.expr;
+                    let key = format!("{}::{}", ident, v.repr.ident);
+                    Some(quote! { (#key, #example) })
+                })
+                .collect(),
+            ItemData::Struct(ItemDataStruct { ty_attrs, .. })
+            | ItemData::Union(ItemDataUnion { ty_attrs }) => ty_attrs
+                .example
+                .iter()
+                .map(|example| {
+                    let key = ident.to_string();
+                    let example = &example.expr;
+                    quote! { (#key, #example) }
+                })
+                .collect::<Vec<_>>(),
+        };
+        quote! {
+            impl#impl_generics ::rocket_simple_responder::testing::Examples for #ident#ty_generics
+            #where_clauses
+            {
+                fn examples() -> ::std::vec::Vec<(&'static str, Self)> {
+                    ::std::vec![#(#examples),*]
+                }
+            }
+        }
+    };
+    #[cfg(not(feature = "testing"))]
+    let examples_impl = quote! {};
+
+    #[cfg(feature = "tonic")]
+    let grpc_impl = grpc_impl(&ident, &input.generics, &input.data);
+    #[cfg(not(feature = "tonic"))]
+    let grpc_impl = quote! {};
+
+    let details_impl = details_impl(&ident, &input.generics, &input.data);
+    let outcome_impl = outcome_impl(&ident, &input.generics);
+
+    #[cfg(feature = "registry")]
+    let registry_impl = registry_impl(&ident, &input.data);
+    #[cfg(not(feature = "registry"))]
+    let registry_impl = quote! {};
+
+    let responder_impl = match input.data {
+        ItemData::Enum(data) => {
+            // delegate arms move their field out of `self`, so they are logged beforehand
+            let log_arms: Vec<_> = data
+                .variants
+                .iter()
+                .filter(|v| v.fields_attrs.delegate.is_some())
+                .filter_map(|v| {
+                    let lines = log_lines(
+                        &v.variant_attrs,
+                        false,
+                        quote! { &self },
+                        quote! { &self },
+                        &mut expanded_where_clauses,
+                    );
+                    if lines.is_empty() {
+                        return None;
+                    }
+                    let ident = &v.repr.ident;
+                    let patterns = fields_pat(&v.repr.fields, &[], false);
+                    Some(quote! { Self::#ident#patterns => { #lines } })
+                })
+                .collect();
+            let log = if log_arms.is_empty() {
+                quote! {}
+            } else {
+                quote! {
+                    #[allow(unreachable_patterns)]
+                    match &self {
+                        #(#log_arms)*
+                        _ => {}
+                    }
+                }
+            };
+            let arms = data.variants.iter().map(|v| {
+                let ident = &v.repr.ident;
+                if let Some(delegate) = &v.fields_attrs.delegate {
+                    let delegate_ident = &delegate.ident;
+                    let delegate_ty = &delegate.ty;
+                    let patterns = fields_pat(&v.repr.fields, &[delegate_ident], false);
+                    expanded_where_clauses.predicates.push(
+                        parse_quote! { #delegate_ty: ::rocket::response::Responder<'_r, '_o> },
+                    );
+                    let body = delegate_response(&v.variant_attrs, delegate_ident);
+                    quote! {
+                        Self::#ident#patterns => #body,
+                    }
+                } else if v.variant_attrs.code.is_some() {
+                    let patterns = fields_pat(&v.repr.fields, &v.fields_attrs.bindings(), true);
+                    let body = code_response(
+                        item_ident,
+                        Some(ident),
+                        &v.repr.fields,
+                        &v.variant_attrs,
+                        &v.fields_attrs,
+                        &mut expanded_where_clauses,
+                    );
+                    quote! {
+                        Self::#ident#patterns => #body
+                    }
+                } else {
+                    panic!("should have one of delegate or code");
+                }
+            });
+            quote! {
+                #log
+                match self { #(#arms)* }
+            }
+        }
+        ItemData::Struct(data) => {
+            if let Some(delegate) = data.fields_attrs.delegate {
+                let delegate_ident = delegate.ident;
+                let delegate_ty = &delegate.ty;
+                expanded_where_clauses
+                    .predicates
+                    .push(parse_quote! { #delegate_ty: ::rocket::response::Responder<'_r, '_o> });
+                let patterns = fields_pat(&data.repr.fields, &[&delegate_ident], false);
+                let log = log_lines(
+                    &data.ty_attrs,
+                    false,
+                    quote! { &self },
+                    quote! { &self },
+                    &mut expanded_where_clauses,
+                );
+                let body = delegate_response(&data.ty_attrs, &delegate_ident);
+                quote! {{
+                    #log
+                    let Self#patterns = self;
+                    #body
+                }}
+            } else if data.ty_attrs.code.is_some() {
+                let patterns = fields_pat(&data.repr.fields, &data.fields_attrs.bindings(), true);
+                let body = code_response(
+                    &ident,
+                    None,
+                    &data.repr.fields,
+                    &data.ty_attrs,
+                    &data.fields_attrs,
+                    &mut expanded_where_clauses,
+                );
+                quote! {{
+                    let Self#patterns = self;
+                    #body
+                }}
+            } else {
+                panic!("should have one of delegate or code");
+            }
+        }
+        ItemData::Union(data) => code_response(
+            &ident,
+            None,
+            &Fields::Unit,
+            &data.ty_attrs,
+            &FieldsAttrs::default(),
+            &mut expanded_where_clauses,
+        ),
+    };
+
+    quote! {
+        impl#impl_generics ::rocket::response::Responder<'_r, '_o> for #ident#ty_generics
+        #expanded_where_clauses
+        {
+            fn respond_to(self, request: &'_r ::rocket::Request<'_>) -> ::rocket::response::Result<'_o> {
+                #responder_impl
+            }
+        }
+
+        #details_impl
+        #outcome_impl
+        #examples_impl
+        #grpc_impl
+        #registry_impl
+    }
+}
+
+/// Implements `ErrorDetails`, with the declared status of each variant.
+///
+/// Delegate variants use the status of the delegate when its type implements `ErrorDetails`,
+/// and else their code or 500.
+fn details_impl(
+    ident: &Ident,
+    generics: &syn::Generics,
+    data: &ItemData,
+) -> proc_macro2::TokenStream {
+    let (impl_generics, ty_generics, where_clauses) = generics.split_for_impl();
+    let mut where_clause: WhereClause = parse_quote! { where };
+    if let Some(where_clauses) = where_clauses {
+        where_clause
+            .predicates
+            .extend(where_clauses.predicates.iter().cloned());
+    }
+    let mut status_arms = Vec::new();
+    let mut path_arms = Vec::new();
+    let mut arm = |pat: proc_macro2::TokenStream,
+                   fields: &Fields,
+                   attrs: &TypeAttrs,
+                   fields_attrs: &FieldsAttrs| {
+        let code = attrs
+            .code
+            .as_ref()
+            .map(|code| {
+                let code = code.code;
+                quote! { ::rocket::http::Status::from_code(#code).unwrap() }
+            })
+            .unwrap_or_else(|| quote! { ::rocket::http::Status::InternalServerError });
+        let status_arm = match (&fields_attrs.delegate, &fields_attrs.errors) {
+            (Some(delegate), _) => {
+                let delegate_ident = &delegate.ident;
+                let patterns = fields_pat(fields, &[delegate_ident], false);
+                let delegate = binding(delegate_ident);
+                quote! {
+                    #pat#patterns => {
+                        use ::rocket_simple_responder::__private::{ViaErrorDetails as _, ViaFallback as _};
+                        (&::rocket_simple_responder::__private::DelegateStatus(#delegate))
+                            .delegate_status()
+                            .unwrap_or(#code)
+                    }
+                }
+            }
+            (None, Some(marker)) if attrs.status_from_errors => {
+                let errors_ident = &marker.ident;
+                let ty = &marker.ty;
+                where_clause
+                    .predicates
+                    .push(parse_quote! { #ty: ::rocket_simple_responder::ErrorList });
+                let patterns = fields_pat(fields, &[errors_ident], false);
+                let errors = binding(errors_ident);
+                quote! {
+                    #pat#patterns => ::rocket_simple_responder::__private::severest(
+                        &::rocket_simple_responder::ErrorList::aggregated(#errors),
+                    )
+                    .unwrap_or(#code)
+                }
+            }
+            _ => {
+                let patterns = fields_pat(fields, &[], false);
+                quote! { #pat#patterns => #code }
+            }
+        };
+        status_arms.push(status_arm);
+        if let Some(marker) = &fields_attrs.path {
+            let path_ident = &marker.ident;
+            let ty = &marker.ty;
+            where_clause
+                .predicates
+                .push(parse_quote! { #ty: ::std::fmt::Display });
+            let patterns = fields_pat(fields, &[path_ident], false);
+            let path = binding(path_ident);
+            path_arms.push(quote! {
+                #pat#patterns => ::std::option::Option::Some(::std::string::ToString::to_string(#path)),
+            });
+        }
+    };
+    let status = match data {
+        ItemData::Enum(data) => {
+            for v in &data.variants {
+                let variant_ident = &v.repr.ident;
+                arm(
+                    quote! { Self::#variant_ident },
+                    &v.repr.fields,
+                    &v.variant_attrs,
+                    &v.fields_attrs,
+                );
+            }
+            quote! { match self { #(#status_arms,)* } }
+        }
+        ItemData::Struct(data) => {
+            arm(
+                quote! { Self },
+                &data.repr.fields,
+                &data.ty_attrs,
+                &data.fields_attrs,
+            );
+            quote! { match self { #(#status_arms,)* } }
+        }
+        ItemData::Union(data) => {
+            let code = data.ty_attrs.code.as_ref().expect("should have code").code;
+            quote! { ::rocket::http::Status::from_code(#code).unwrap() }
+        }
+    };
+    let path = if path_arms.is_empty() {
+        quote! {}
+    } else {
+        quote! {
+            fn path(&self) -> ::std::option::Option<::std::string::String> {
+                #[allow(unreachable_patterns)]
+                match self {
+                    #(#path_arms)*
+                    _ => ::std::option::Option::None,
+                }
+            }
+        }
+    };
+    quote! {
+        impl#impl_generics ::rocket_simple_responder::ErrorDetails for #ident#ty_generics
+        #where_clause
+        {
+            fn status(&self) -> ::rocket::http::Status {
+                #status
+            }
+
+            #path
+        }
+    }
+}
+
+/// Implements the conversion to the failure outcome of request guards, with the status given by
+/// `ErrorDetails`.
+fn outcome_impl(ident: &Ident, generics: &syn::Generics) -> proc_macro2::TokenStream {
+    let (impl_generics, ty_generics, where_clauses) = generics.split_for_impl();
+    let mut where_clause: WhereClause = parse_quote! { where };
+    if let Some(where_clauses) = where_clauses {
+        where_clause
+            .predicates
+            .extend(where_clauses.predicates.iter().cloned());
+    }
+    // the implementation of `ErrorDetails` may add bounds of its own
+    where_clause
+        .predicates
+        .push(parse_quote! { #ident#ty_generics: ::rocket_simple_responder::ErrorDetails });
+    let mut from_generics = generics.clone();
+    from_generics.params.push(parse_quote!(_S));
+    let (from_impl_generics, _, _) = from_generics.split_for_impl();
+    quote! {
+        impl#impl_generics #ident#ty_generics
+        #where_clause
+        {
+            /// Fails a request guard with this error and its status.
+            pub fn into_outcome<_S>(self) -> ::rocket::request::Outcome<_S, Self> {
+                ::rocket::outcome::Outcome::Failure((
+                    ::rocket_simple_responder::ErrorDetails::status(&self),
+                    self,
+                ))
+            }
+        }
+
+        impl#from_impl_generics ::std::convert::From<#ident#ty_generics>
+            for ::rocket::request::Outcome<_S, #ident#ty_generics>
+        #where_clause
+        {
+            fn from(error: #ident#ty_generics) -> Self {
+                error.into_outcome()
+            }
+        }
+    }
+}
+
+/// Implements the conversion to `tonic::Status`, with the `Display` message of the error.
+#[cfg(feature = "tonic")]
+fn grpc_impl(ident: &Ident, generics: &syn::Generics, data: &ItemData) -> proc_macro2::TokenStream {
+    let (impl_generics, ty_generics, where_clauses) = generics.split_for_impl();
+    let mut where_clause: WhereClause = parse_quote! { where };
+    if let Some(where_clauses) = where_clauses {
+        where_clause
+            .predicates
+            .extend(where_clauses.predicates.iter().cloned());
+    }
+    let mut arm = |pat: proc_macro2::TokenStream,
+                   fields: &Fields,
+                   attrs: &TypeAttrs,
+                   fields_attrs: &FieldsAttrs| {
+        match (&fields_attrs.delegate, &attrs.grpc) {
+            (Some(delegate), None) => {
+                let delegate_ident = &delegate.ident;
+                let delegate_ty = &delegate.ty;
+                let patterns = fields_pat(fields, &[delegate_ident], false);
+                let delegate = binding(delegate_ident);
+                where_clause.predicates.push(parse_quote! {
+                    #delegate_ty: ::std::convert::Into<::rocket_simple_responder::__private::tonic::Status>
+                });
+                quote! {
+                    #pat#patterns => ::std::convert::Into::into(#delegate),
+                }
+            }
+            (_, grpc) => {
+                // an explicit grpc code takes precedence over the delegate
+                let grpc_code = match grpc {
+                    Some(grpc) => grpc.code.clone(),
+                    None => {
+                        let code = attrs.code.as_ref().expect("should have code").code;
+                        format_ident!("{}", attrs::default_grpc_code(code))
+                    }
+                };
+                let patterns = fields_pat(fields, &[], false);
+                // like the message of responses, so that the sensitive fields aren't rendered
+                let message = if fields_attrs.sensitive.is_empty() {
+                    quote! { ::std::string::ToString::to_string(&error) }
+                } else {
+                    match attrs
+                        .message
+                        .as_ref()
+                        .map(|m| m.value.value())
+                        .or_else(|| attrs.display.clone())
+                    {
+                        Some(message) => quote! { #message },
+                        None => quote! { ::rocket_simple_responder::REDACTED },
+                    }
+                };
+                quote! {
+                    #pat#patterns => ::rocket_simple_responder::__private::tonic::Status::new(
+                        ::rocket_simple_responder::__private::tonic::Code::#grpc_code,
+                        #message,
+                    ),
+                }
+            }
+        }
+    };
+    let arms = match data {
+        ItemData::Enum(data) => data
+            .variants
+            .iter()
+            .map(|v| {
+                let variant_ident = &v.repr.ident;
+                arm(
+                    quote! { #ident::#variant_ident },
+                    &v.repr.fields,
+                    &v.variant_attrs,
+                    &v.fields_attrs,
+                )
+            })
+            .collect(),
+        ItemData::Struct(data) => vec![arm(
+            quote! { #ident },
+            &data.repr.fields,
+            &data.ty_attrs,
+            &data.fields_attrs,
+        )],
+        ItemData::Union(data) => vec![arm(
+            quote! { _ },
+            &Fields::Unit,
+            &data.ty_attrs,
+            &FieldsAttrs::default(),
+        )],
+    };
+    quote! {
+        impl#impl_generics ::std::convert::From<#ident#ty_generics>
+            for ::rocket_simple_responder::__private::tonic::Status
+        #where_clause
+        {
+            fn from(error: #ident#ty_generics) -> Self {
+                match error { #(#arms)* }
+            }
+        }
+    }
+}
+
+/// Submits the descriptor of the type to the registry.
+#[cfg(feature = "registry")]
+fn registry_impl(ident: &Ident, data: &ItemData) -> proc_macro2::TokenStream {
+    let variant = |name: Option<&Ident>, attrs: &TypeAttrs, fields_attrs: &FieldsAttrs| {
+        let name = match name {
+            Some(name) => {
+                let name = name.to_string();
+                quote! { ::std::option::Option::Some(#name) }
+            }
+            None => quote! { ::std::option::Option::None },
+        };
+        let code = match (&fields_attrs.delegate, &attrs.code) {
+            (None, Some(code)) => {
+                let code = code.code;
+                quote! { ::std::option::Option::Some(#code) }
+            }
+            _ => quote! { ::std::option::Option::None },
+        };
+        let error_code = match attrs.error_code.as_ref().and_then(|e| e.code.as_ref()) {
+            Some(error_code) => quote! { ::std::option::Option::Some(#error_code) },
+            None => quote! { ::std::option::Option::None },
+        };
+        let delegate = match &fields_attrs.delegate {
+            Some(delegate) => {
+                let ty = &delegate.ty;
+                quote! { ::std::option::Option::Some(::std::stringify!(#ty)) }
+            }
+            None => quote! { ::std::option::Option::None },
+        };
+        quote! {
+            ::rocket_simple_responder::registry::VariantDescriptor {
+                name: #name,
+                code: #code,
+                error_code: #error_code,
+                delegate: #delegate,
+            }
+        }
+    };
+    let variants: Vec<_> = match data {
+        ItemData::Enum(data) => data
+            .variants
+            .iter()
+            .map(|v| variant(Some(&v.repr.ident), &v.variant_attrs, &v.fields_attrs))
+            .collect(),
+        ItemData::Struct(data) => vec![variant(None, &data.ty_attrs, &data.fields_attrs)],
+        ItemData::Union(data) => vec![variant(None, &data.ty_attrs, &FieldsAttrs::default())],
+    };
+    let type_name = ident.to_string();
+    quote! {
+        const _: () = {
+            ::rocket_simple_responder::__private::inventory::submit! {
+                #![crate = ::rocket_simple_responder::__private]
+                ::rocket_simple_responder::registry::ErrorDescriptor {
+                    type_name: #type_name,
+                    module_path: ::std::module_path!(),
+                    variants: &[#(#variants),*],
+                }
+            }
+        };
+    }
+}
+
+/// Builds a response from the `Display` message of `self`, as plain text unless a formatter is
+/// specified, or from the JSON serialization of the `body` field. The `content_type` argument
+/// replaces the content type of any of them.
+///
+/// The message is borrowed when it is static, given by the `message` argument or as a literal
+/// of thiserror's `error` attribute, and only allocated when overridden.
+///
+/// The errors of the `errors` field are listed after the message, or passed to the formatter.
+/// Those of the `validation` field are listed along with the message in a JSON body.
+///
+/// The status is resolved by the `code_with` function if any, or else is the most severe among
+/// the aggregated errors when no code was specified. With the `reporting` feature, it is reported
+/// to the managed `Reporter` if the type implements `Error`.
+///
+/// With `forward`, nothing is built and the status is returned as an error, for Rocket to
+/// respond with its catcher.
+///
+/// Both the status code and the message can be overridden at runtime through the
+/// `error_overrides.<key>` table of Rocket's configuration, where `key` is the type name,
+/// followed by `::<variant>` for enum variants.
+///
+/// The generated arm only resolves the status, the message, the headers and the body, and
+/// leaves the rest to the helpers of the runtime crate, so that it stays small.
+///
+/// The fields in `fields_attrs` must already be bound by reference, to their `binding`.
+fn code_response(
+    item: &Ident,
+    variant: Option<&Ident>,
+    fields: &Fields,
+    attrs: &TypeAttrs,
+    fields_attrs: &FieldsAttrs,
+    where_clause: &mut WhereClause,
+) -> proc_macro2::TokenStream {
+    let code = attrs.code.as_ref().expect("should have code").code;
+    // the `Display` message may render the sensitive fields, so it is replaced as a whole
+    let static_message = match attrs
+        .message
+        .as_ref()
+        .map(|m| m.value.value())
+        .or_else(|| attrs.display.clone())
+    {
+        Some(message) => quote! { ::std::option::Option::Some(#message) },
+        None if !fields_attrs.sensitive.is_empty() => {
+            quote! { ::std::option::Option::Some(::rocket_simple_responder::REDACTED) }
+        }
+        None => quote! { ::std::option::Option::None },
+    };
+    let type_name = item.to_string();
+    let key = match variant {
+        Some(variant) => format!("{}::{}", item, variant),
+        None => type_name.clone(),
+    };
+    let error = if fields_attrs.sensitive.is_empty() {
+        quote! { &self }
+    } else {
+        redacted_debug(item, variant, fields, &fields_attrs.sensitive, where_clause)
+    };
+    let log = log_lines(attrs, true, quote! { &msg }, error, where_clause);
+    let status = match &attrs.code_with {
+        Some(code_with) => {
+            let expr = &code_with.expr;
+            quote! {
+                ::rocket_simple_responder::StatusFn::<Self, _>::status(&(#expr), &self, request)
+            }
+        }
+        None if attrs.status_from_errors => quote! {
+            ::rocket_simple_responder::__private::severest(&aggregated_errors)
+                .unwrap_or_else(|| ::rocket::http::Status::from_code(#code).unwrap())
+        },
+        None => quote! { ::rocket::http::Status::from_code(#code).unwrap() },
+    };
+    let report = if cfg!(feature = "reporting") {
+        // features are unified across the dependency graph, so types that don't implement
+        // `Error` are skipped rather than required to
+        quote! {{
+            use ::rocket_simple_responder::__private::{ViaError as _, ViaNoError as _};
+            (&::rocket_simple_responder::__private::Reportable(&self)).report(request, status);
+        }}
+    } else {
+        quote! {}
+    };
+    let status = quote! {
+        let status = ::rocket_simple_responder::__private::override_status(
+            request,
+            #key,
+            &|| #status,
+        );
+    };
+    let msg = quote! {
+        let msg = ::rocket_simple_responder::__private::override_message(
+            request,
+            #key,
+            #static_message,
+            &self,
+        );
+    };
+    if attrs.forward.is_some() {
+        // the message is only needed for logging, as the catcher provides the body
+        let msg = if log.is_empty() {
+            quote! {}
+        } else {
+            msg
+        };
+        return quote! {{
+            #status
+            #msg
+            #log
+            #report
+            Err(status)
+        }};
+    }
+    let aggregated_errors = match &fields_attrs.errors {
+        Some(marker) => {
+            let ident = binding(&marker.ident);
+            let ty = &marker.ty;
+            where_clause
+                .predicates
+                .push(parse_quote! { #ty: ::rocket_simple_responder::ErrorList });
+            quote! {
+                let aggregated_errors = ::rocket_simple_responder::ErrorList::aggregated(#ident);
+            }
+        }
+        None => quote! {},
+    };
+    let mut headers = Vec::new();
+    if let Some(challenge) = &attrs.challenge {
+        headers.push(challenge_header(challenge, &fields_attrs.challenge_params));
+    }
+    let error_code = match attrs.error_code.as_ref().and_then(|e| e.code.as_ref()) {
+        Some(error_code) => {
+            headers.push(quote! {
+                headers.push(("X-Error-Code", ::std::borrow::Cow::Borrowed(#error_code)));
+            });
+            quote! { ::std::option::Option::Some(#error_code) }
+        }
+        None => quote! { ::std::option::Option::None },
+    };
+    if let Some(marker) = &fields_attrs.retry_after {
+        let ident = binding(&marker.ident);
+        let ty = &marker.ty;
+        where_clause
+            .predicates
+            .push(parse_quote! { #ty: ::rocket_simple_responder::RetryAfter });
+        headers.push(quote! {
+            if let Some(value) = ::rocket_simple_responder::RetryAfter::retry_after(#ident) {
+                headers.push(("Retry-After", ::std::borrow::Cow::Owned(value)));
+            }
+        });
+    }
+    let rate_limits = [
+        (
+            "RateLimit-Limit",
+            fields_attrs
+                .ratelimit_limit
+                .as_ref()
+                .map(|m| (binding(&m.ident), &m.ty)),
+        ),
+        (
+            "RateLimit-Remaining",
+            fields_attrs
+                .ratelimit_remaining
+                .as_ref()
+                .map(|m| (binding(&m.ident), &m.ty)),
+        ),
+        (
+            "RateLimit-Reset",
+            fields_attrs
+                .ratelimit_reset
+                .as_ref()
+                .map(|m| (binding(&m.ident), &m.ty)),
+        ),
+    ];
+    if let Some(marker) = &fields_attrs.location {
+        let ident = binding(&marker.ident);
+        let ty = &marker.ty;
+        where_clause
+            .predicates
+            .push(parse_quote! { #ty: ::std::fmt::Display });
+        // the status may have been resolved or overridden to one that isn't a redirection
+        headers.push(quote! {
+            if status.class() == ::rocket::http::StatusClass::Redirection {
+                headers.push((
+                    "Location",
+                    ::std::borrow::Cow::Owned(::std::string::ToString::to_string(#ident)),
+                ));
+            }
+        });
+    }
+    for (header_name, field) in rate_limits.iter() {
+        if let Some((ident, ty)) = field {
+            where_clause
+                .predicates
+                .push(parse_quote! { #ty: ::rocket_simple_responder::RateLimitValue });
+            headers.push(quote! {
+                if let Some(value) = ::rocket_simple_responder::RateLimitValue::rate_limit_value(#ident) {
+                    headers.push((
+                        #header_name,
+                        ::std::borrow::Cow::Owned(::std::string::ToString::to_string(&value)),
+                    ));
+                }
+            });
+        }
+    }
+    let (headers, headers_arg) = if headers.is_empty() {
+        (quote! {}, quote! { ::std::vec::Vec::new() })
+    } else {
+        let headers = quote! {
+            let mut headers = ::std::vec::Vec::new();
+            #(#headers)*
+        };
+        (headers, quote! { headers })
+    };
+    // the content type, unless given by the body itself along with its content
+    let (body_content_type, body) = if let Some(marker) = &fields_attrs.body {
+        let ident = binding(&marker.ident);
+        let ty = &marker.ty;
+        where_clause
+            .predicates
+            .push(parse_quote! { #ty: ::rocket_simple_responder::__private::serde::Serialize });
+        let body = quote! {
+            match ::rocket_simple_responder::__private::serde_json::to_string(#ident) {
+                Ok(body) => body,
+                Err(_) => return Err(::rocket::http::Status::InternalServerError),
+            }
+        };
+        (Some(quote! { ::rocket::http::ContentType::JSON }), body)
+    } else if let Some(marker) = &fields_attrs.validation {
+        let ident = binding(&marker.ident);
+        let ty = &marker.ty;
+        where_clause.predicates.push(parse_quote! {
+            #ty: ::std::borrow::Borrow<::rocket_simple_responder::__private::validator::ValidationErrors>
+        });
+        let body = quote! {
+            ::rocket_simple_responder::__private::validation_body(
+                &msg,
+                ::std::borrow::Borrow::borrow(#ident),
+            )
+        };
+        (Some(quote! { ::rocket::http::ContentType::JSON }), body)
+    } else if let Some(formatter) = &attrs.formatter {
+        let formatter = &formatter.path;
+        // `ErrorInfo` is non-exhaustive, so the fields that aren't empty are set after `new`
+        let mut fields = Vec::new();
+        if let Some(variant) = variant {
+            let variant = variant.to_string();
+            fields.push(quote! { info.variant = ::std::option::Option::Some(#variant); });
+        }
+        if attrs
+            .error_code
+            .as_ref()
+            .and_then(|e| e.code.as_ref())
+            .is_some()
+        {
+            fields.push(quote! { info.error_code = #error_code; });
+        }
+        if fields_attrs.errors.is_some() {
+            fields.push(quote! { info.errors = aggregated_errors; });
+        }
+        if cfg!(feature = "json") && !fields_attrs.exposed.is_empty() {
+            let members = fields_attrs.exposed.iter().map(|m| {
+                let name = &m.name;
+                let ident = binding(&m.ident);
+                let ty = &m.ty;
+                if fields_attrs.sensitive.iter().any(|s| s.ident == m.ident) {
+                    return quote! {
+                        (
+                            #name,
+                            ::rocket_simple_responder::__private::serde_json::Value::from(
+                                ::rocket_simple_responder::REDACTED,
+                            ),
+                        )
+                    };
+                }
+                where_clause.predicates.push(
+                    parse_quote! { #ty: ::rocket_simple_responder::__private::serde::Serialize },
+                );
+                quote! {
+                    (
+                        #name,
+                        ::rocket_simple_responder::__private::serde_json::to_value(#ident)
+                            .map_err(|_| ::rocket::http::Status::InternalServerError)?,
+                    )
+                }
+            });
+            fields.push(quote! { info.exposed = ::std::vec![#(#members),*]; });
+        }
+        let info = if fields.is_empty() {
+            quote! { info }
+        } else {
+            quote! { mut info }
+        };
+        let body = quote! {{
+            let #info = ::rocket_simple_responder::ErrorInfo::new(#type_name, status, &msg);
+            #(#fields)*
+            <#formatter as ::rocket_simple_responder::ErrorFormatter>::format(&info, request)
+        }};
+        (None, body)
+    } else if fields_attrs.errors.is_some() {
+        let body = quote! {
+            ::rocket_simple_responder::__private::plain(&msg, &aggregated_errors)
+        };
+        (Some(quote! { ::rocket::http::ContentType::Plain }), body)
+    } else {
+        (
+            Some(quote! { ::rocket::http::ContentType::Plain }),
+            quote! { msg },
+        )
+    };
+    // the body is still left out at runtime if the status is overridden with one that can't
+    // carry it
+    let body = match (&attrs.content_type, body_content_type) {
+        _ if !has_body(code) => quote! { ::std::option::Option::None },
+        (Some(content_type), body_content_type) => {
+            let MediaType { top, sub, params } = &content_type.media_type;
+            let params = if params.is_empty() {
+                quote! {}
+            } else {
+                let params = params
+                    .iter()
+                    .map(|(name, value)| quote! { (#name, #value) });
+                quote! { .with_params(&[#(#params),*][..]) }
+            };
+            let body = match body_content_type {
+                Some(_) => body,
+                None => quote! { (#body).1 },
+            };
+            quote! {
+                ::std::option::Option::Some((
+                    ::rocket::http::ContentType::new(#top, #sub)#params,
+                    ::rocket_simple_responder::__private::into_body(#body),
+                ))
+            }
+        }
+        (None, Some(content_type)) => quote! {
+            ::std::option::Option::Some((
+                #content_type,
+                ::rocket_simple_responder::__private::into_body(#body),
+            ))
+        },
+        (None, None) => quote! {{
+            let (content_type, body) = #body;
+            ::std::option::Option::Some((
+                content_type,
+                ::rocket_simple_responder::__private::into_body(body),
+            ))
+        }},
+    };
+    quote! {{
+        #aggregated_errors
+        #status
+        #msg
+        #log
+        #report
+        #headers
+        let body = #body;
+        ::rocket_simple_responder::__private::respond(status, #headers_arg, body)
+    }}
+}
+
+/// Logs the response with the `logging` feature, at the level given by the `log` argument or
+/// else, with `from_status`, derived from the resolved `status`: a line with the request
+/// context and `message`, then one with the `Debug` representation `error`.
+fn log_lines(
+    attrs: &TypeAttrs,
+    from_status: bool,
+    message: proc_macro2::TokenStream,
+    error: proc_macro2::TokenStream,
+    where_clause: &mut WhereClause,
+) -> proc_macro2::TokenStream {
+    if cfg!(not(feature = "logging")) {
+        return quote! {};
+    }
+    let level = match &attrs.log {
+        Some(log) => match &log.level {
+            Some(level) => quote! { ::rocket_simple_responder::__private::Level::#level },
+            None => return quote! {},
+        },
+        None if from_status => quote! { ::rocket_simple_responder::__private::Level::of(status) },
+        None => return quote! {},
+    };
+    where_clause
+        .predicates
+        .push(parse_quote! { Self: ::std::fmt::Debug });
+    quote! {
+        ::rocket_simple_responder::__private::log(request, #level, #message, #error);
+    }
+}
+
+/// A `Debug` representation of `self`, laid out like the derived one, with `***` in place of the
+/// `sensitive` fields.
+fn redacted_debug(
+    item: &Ident,
+    variant: Option<&Ident>,
+    fields: &Fields,
+    sensitive: &[Sensitive],
+    where_clause: &mut WhereClause,
+) -> proc_macro2::TokenStream {
+    let (path, name) = match variant {
+        Some(variant) => (quote! { Self::#variant }, variant.unraw().to_string()),
+        None => (quote! { Self }, item.unraw().to_string()),
+    };
+    let bindings: Vec<_> = match fields {
+        Fields::Named(fields) => fields
+            .named
+            .iter()
+            .filter_map(|f| Some((f.ident.clone()?, &f.ty)))
+            .collect(),
+        _ => fields
+            .iter()
+            .enumerate()
+            .map(|(i, f)| (format_ident!("_{}", i), &f.ty))
+            .collect(),
+    };
+    // sensitive fields aren't bound, so that they can't be rendered
+    let (patterns, values): (Vec<_>, Vec<_>) = bindings
+        .iter()
+        .map(|(ident, ty)| {
+            let named = matches!(fields, Fields::Named(_));
+            if sensitive.iter().any(|s| s.ident == *ident) {
+                let pattern = if named {
+                    quote! { #ident: _ }
+                } else {
+                    quote! { _ }
+                };
+                (pattern, quote! { &::rocket_simple_responder::Redacted(()) })
+            } else {
+                where_clause
+                    .predicates
+                    .push(parse_quote! { #ty: ::std::fmt::Debug });
+                let binding = binding(ident);
+                let pattern = if named {
+                    quote! { #ident: #binding }
+                } else {
+                    quote! { #binding }
+                };
+                (pattern, quote! { #binding })
+            }
+        })
+        .unzip();
+    let (pattern, debug) = match fields {
+        Fields::Named(_) => {
+            let names = bindings.iter().map(|(ident, _)| ident.unraw().to_string());
+            (
+                quote! { { #(#patterns),* } },
+                quote! { f.debug_struct(#name)#(.field(#names, #values))*.finish() },
+            )
+        }
+        Fields::Unnamed(_) => (
+            quote! { (#(#patterns),*) },
+            quote! { f.debug_tuple(#name)#(.field(#values))*.finish() },
+        ),
+        Fields::Unit => (quote! {}, quote! { f.write_str(#name) }),
+    };
+    quote! {
+        &::rocket_simple_responder::__private::DebugFn(|f: &mut ::std::fmt::Formatter<'_>| {
+            #[allow(unreachable_patterns)]
+            match &self {
+                #path#pattern => #debug,
+                _ => ::std::result::Result::Ok(()),
+            }
+        })
+    }
+}
+
+/// Responds with the delegate bound to `delegate_ident`. When a `log` level is specified, the
+/// delegating type or variant is logged at that level, or not at all for `off`, and the logging
+/// of the delegate is turned off, so that each error is logged once.
+fn delegate_response(attrs: &TypeAttrs, delegate_ident: &Ident) -> proc_macro2::TokenStream {
+    let delegate = binding(delegate_ident);
+    let respond = quote! { ::rocket::response::Responder::respond_to(#delegate, request) };
+    if cfg!(feature = "logging") && attrs.log.is_some() {
+        quote! { ::rocket_simple_responder::__private::delegate(request, || #respond) }
+    } else {
+        respond
+    }
+}
+
+/// Sets the `WWW-Authenticate` (or `Proxy-Authenticate` for 407) header described by
+/// `challenge`, appending the parameters taken from fields as quoted strings.
+///
+/// The header is only set when the resolved status is 401 or 407.
+fn challenge_header(
+    challenge: &ChallengeArg,
+    params: &[ChallengeParam],
+) -> proc_macro2::TokenStream {
+    let mut value = challenge.scheme.clone();
+    for (i, (name, param)) in challenge.params.iter().enumerate() {
+        value.push_str(if i == 0 { " " } else { ", " });
+        value.push_str(&format!(
+            "{}=\"{}\"",
+            name,
+            param.replace('\\', "\\\\").replace('"', "\\\"")
+        ));
+    }
+    let value = if params.is_empty() {
+        quote! { ::std::borrow::Cow::Borrowed(#value) }
+    } else {
+        let field_params = params.iter().enumerate().map(|(i, param)| {
+            let prefix = format!(
+                "{}{}=\"",
+                if i == 0 && challenge.params.is_empty() {
+                    " "
+                } else {
+                    ", "
+                },
+                param.name
+            );
+            let ident = binding(&param.ident);
+            quote! {
+                challenge.push_str(#prefix);
+                for c in ::std::string::ToString::to_string(#ident).chars() {
+                    match c {
+                        '"' | '\\' => {
+                            challenge.push('\\');
+                            challenge.push(c);
+                        }
+                        c if c.is_control() => {}
+                        c => challenge.push(c),
+                    }
+                }
+                challenge.push('"');
+            }
+        });
+        quote! {{
+            let mut challenge = ::std::string::String::from(#value);
+            #(#field_params)*
+            ::std::borrow::Cow::Owned(challenge)
+        }}
+    };
+    quote! {
+        if let Some(name) = ::rocket_simple_responder::__private::challenge_header(status) {
+            headers.push((name, #value));
+        }
+    }
+}
+
+/// Builds a pattern destructuring `fields` that only binds the fields in `bindings`, each to its
+/// `binding`.
+///
+/// Unnamed fields are identified by `_<index>`.
+fn fields_pat(fields: &Fields, bindings: &[&Ident], by_ref: bool) -> proc_macro2::TokenStream {
+    let by_ref = if by_ref {
+        quote! { ref }
+    } else {
+        quote! {}
+    };
+    match fields {
+        Fields::Named(fields) => {
+            let fields = fields
+                .named
+                .iter()
+                .filter_map(|f| f.ident.as_ref())
+                .filter(|ident| bindings.contains(ident))
+                .map(|ident| {
+                    let binding = binding(ident);
+                    quote! { #ident: #by_ref #binding }
+                });
+            quote! {{ #(#fields,)* .. }}
+        }
+        Fields::Unnamed(fields) => {
+            let fields = fields.unnamed.iter().enumerate().map(|(i, _f)| {
+                let ident = format_ident!("_{}", i);
+                if bindings.contains(&&ident) {
+                    let binding = binding(&ident);
+                    quote! { #by_ref #binding }
+                } else {
+                    quote! { _ }
+                }
+            });
+            quote! {(#(#fields,)*)}
+        }
+        Fields::Unit => {
+            quote! {}
+        }
+    }
+}
+
+/// The local variable the field `ident` is bound to, prefixed so that fields named like the
+/// variables of the generated code, such as `status` or `request`, don't shadow them.
+fn binding(ident: &Ident) -> Ident {
+    format_ident!("__field_{}", ident.unraw())
+}
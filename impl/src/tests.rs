@@ -0,0 +1,171 @@
+//! Checks of the expansion that the integration tests can't observe, such as compile errors.
+
+use proc_macro2::TokenStream;
+use quote::quote;
+
+use crate::utils::Item;
+
+fn parse_error(input: TokenStream) -> String {
+    match syn::parse2::<Item>(input) {
+        Ok(_) => panic!("expected an error"),
+        Err(error) => error.to_string(),
+    }
+}
+
+#[test]
+fn log_parse_errors() {
+    assert_eq!(
+        parse_error(quote! {
+            #[response(code = 404, log = "verbose")]
+            struct Error;
+        }),
+        "log must be one of \"off\", \"debug\", \"info\", \"warn\" or \"error\""
+    );
+    assert_eq!(
+        parse_error(quote! {
+            #[response(code = 404, log = "debug", log = "info")]
+            struct Error;
+        }),
+        "duplicate log argument"
+    );
+    assert_eq!(
+        parse_error(quote! {
+            #[response(code = 404, log = debug)]
+            struct Error;
+        }),
+        "expected string literal"
+    );
+}
+
+#[test]
+fn challenge_parse_errors() {
+    assert_eq!(
+        parse_error(quote! {
+            #[response(code = 401, challenge(scheme = "Bearer", error = "invalid_token"))]
+            struct Error {
+                #[response(challenge = "Error")]
+                error: String,
+            }
+        }),
+        "challenge parameter `Error` is already given by the challenge"
+    );
+    assert_eq!(
+        parse_error(quote! {
+            #[response(code = 401, challenge(scheme = "Bearer", realm = "api"))]
+            enum Error {
+                Missing,
+                Invalid {
+                    #[response(challenge)]
+                    realm: String,
+                },
+            }
+        }),
+        "challenge parameter `realm` is already given by the challenge"
+    );
+}
+
+#[test]
+fn expose_parse_errors() {
+    for name in &[
+        "status",
+        "title",
+        "type",
+        "detail",
+        "message",
+        "error_code",
+        "errors",
+    ] {
+        assert_eq!(
+            parse_error(quote! {
+                #[response(code = 404, formatter = ProblemJson)]
+                struct Error {
+                    #[response(expose = #name)]
+                    id: u64,
+                }
+            }),
+            format!(
+                "can't expose a field as `{}`, which is written by the formatters",
+                name
+            )
+        );
+    }
+    assert_eq!(
+        parse_error(quote! {
+            #[response(code = 404, formatter = Json)]
+            struct Error {
+                #[response(expose)]
+                message: String,
+            }
+        }),
+        "can't expose a field as `message`, which is written by the formatters"
+    );
+    assert_eq!(
+        parse_error(quote! {
+            #[response(code = 404, formatter = Json)]
+            struct Error {
+                #[response(expose)]
+                id: u64,
+                #[response(expose = "id")]
+                other_id: u64,
+            }
+        }),
+        "duplicate exposed name `id`"
+    );
+}
+
+#[test]
+fn content_type_parse_errors() {
+    for content_type in &[
+        "text",
+        "text/",
+        "text/plain;",
+        "text/plain; charset",
+        "text/plain; charset=\"utf-8",
+        // Rocket doesn't quote the values it writes
+        "text/plain; title=\"two words\"",
+        "text/plain; title=\"a\\\"b\"",
+    ] {
+        assert_eq!(
+            parse_error(quote! {
+                #[response(code = 404, content_type = #content_type)]
+                struct Error;
+            }),
+            "invalid content type",
+            "{}",
+            content_type
+        );
+    }
+}
+
+#[cfg(feature = "validator")]
+#[test]
+fn validation_parse_errors() {
+    assert_eq!(
+        parse_error(quote! {
+            #[response(code = 422, formatter = Json)]
+            struct Error(#[response(validation)] ValidationErrors);
+        }),
+        "can't specify both validation and formatter"
+    );
+    // including the ones inherited from the enum
+    assert_eq!(
+        parse_error(quote! {
+            #[response(formatter = Json)]
+            enum Error {
+                #[error("invalid input")]
+                Invalid(#[response(validation)] ValidationErrors),
+            }
+        }),
+        "can't specify both validation and formatter"
+    );
+    assert_eq!(
+        parse_error(quote! {
+            #[response(content_type = "application/vnd.acme.error+json")]
+            enum Error {
+                #[error("invalid input")]
+                Invalid(#[response(validation)] ValidationErrors),
+            }
+        }),
+        "can't specify both validation and content_type"
+    );
+}
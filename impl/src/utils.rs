@@ -0,0 +1,537 @@
+use std::collections::HashSet;
+
+use attrs::{CodeArg, ErrorCodeArg, FieldsAttrs, TypeAttrs};
+use proc_macro2::Ident;
+use syn::{
+    parse::{Parse, ParseStream},
+    punctuated::Punctuated,
+    Data, DeriveInput, Error, Generics, Result, Token,
+};
+
+use crate::attrs;
+
+struct Wrap<T>(pub T);
+
+impl<T: Parse> Parse for Wrap<Punctuated<T, Token![,]>> {
+    fn parse(input: ParseStream) -> Result<Self> {
+        Ok(Self(input.parse_terminated(T::parse)?))
+    }
+}
+
+pub struct Item {
+    pub ident: Ident,
+    pub generics: Generics,
+    pub data: ItemData,
+}
+
+impl Parse for Item {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let repr: DeriveInput = input.parse()?;
+        let ty_attrs = TypeAttrs::new(repr.attrs.as_slice())?;
+        let ident = &repr.ident;
+
+        let data = match repr.data {
+            Data::Enum(data) => {
+                if let Some(example) = &ty_attrs.example {
+                    return Err(Error::new_spanned(
+                        example.kw,
+                        "example must be specified on variants",
+                    ));
+                }
+                if let Some(message) = &ty_attrs.message {
+                    return Err(Error::new_spanned(
+                        message.kw,
+                        "message must be specified on variants",
+                    ));
+                }
+                if let Some(forward) = &ty_attrs.forward {
+                    return Err(Error::new_spanned(
+                        forward.kw,
+                        "forward must be specified on variants",
+                    ));
+                }
+                if let Some(ErrorCodeArg {
+                    code: Some(code), ..
+                }) = &ty_attrs.error_code
+                {
+                    return Err(Error::new_spanned(
+                        code,
+                        "error_code of an enum must be auto or specified on variants",
+                    ));
+                }
+                let variants = data
+                    .variants
+                    .iter()
+                    .map(|v| {
+                        let mut variant_attrs = TypeAttrs::new(v.attrs.as_slice())?;
+                        let fields_attrs = FieldsAttrs::new(&v.fields)?;
+                        if fields_attrs.delegate.is_some() && variant_attrs.code.is_some() {
+                            return Err(Error::new_spanned(
+                                fields_attrs.delegate.unwrap().kw,
+                                "can't specify both code and delegate",
+                            ));
+                        }
+                        check_delegate(&variant_attrs, &fields_attrs)?;
+                        forward_code(&mut variant_attrs, &fields_attrs)?;
+                        implicit_errors_code(
+                            &mut variant_attrs,
+                            &fields_attrs,
+                            ty_attrs.code.is_some(),
+                        );
+                        // a type level grpc code only applies along with the type level code
+                        if variant_attrs.code.is_none()
+                            && variant_attrs.grpc.is_none()
+                            && fields_attrs.delegate.is_none()
+                        {
+                            variant_attrs.grpc = ty_attrs.grpc.clone();
+                        }
+                        if variant_attrs.error_code.is_none()
+                            && variant_attrs.forward.is_none()
+                            && fields_attrs.delegate.is_none()
+                        {
+                            variant_attrs.error_code = ty_attrs.error_code.clone();
+                        }
+                        if let Some(error_code) = &mut variant_attrs.error_code {
+                            error_code.resolve(&[ident, &v.ident]);
+                        }
+                        // a type level code_with only applies along with the type level code
+                        if variant_attrs.code.is_none()
+                            && variant_attrs.code_with.is_none()
+                            && fields_attrs.delegate.is_none()
+                        {
+                            variant_attrs.code_with = ty_attrs.code_with.clone();
+                        }
+                        variant_attrs.code = variant_attrs.code.or_else(|| ty_attrs.code.clone());
+                        variant_attrs.log = variant_attrs.log.or_else(|| ty_attrs.log.clone());
+                        variant_attrs.formatter = variant_attrs
+                            .formatter
+                            .or_else(|| ty_attrs.formatter.clone());
+                        variant_attrs.content_type = variant_attrs
+                            .content_type
+                            .or_else(|| ty_attrs.content_type.clone());
+                        check_code_with(&variant_attrs, &fields_attrs)?;
+                        if fields_attrs.delegate.is_none() && variant_attrs.code.is_none() {
+                            return Err(Error::new_spanned(
+                                v.ident.clone(),
+                                "code or delegate must be specified",
+                            ));
+                        }
+                        // a type level challenge only applies to the variants that can carry one
+                        if variant_attrs.challenge.is_none()
+                            && variant_attrs.forward.is_none()
+                            && matches!(&variant_attrs.code, Some(c) if is_challenge_code(c.code))
+                        {
+                            variant_attrs.challenge = ty_attrs.challenge.clone();
+                        }
+                        check_attrs(&variant_attrs, &fields_attrs)?;
+                        Ok(Variant {
+                            repr: v.clone(),
+                            variant_attrs,
+                            fields_attrs,
+                        })
+                    })
+                    .collect::<Result<Vec<_>>>()?;
+                check_error_codes(&variants)?;
+                ItemData::Enum(ItemDataEnum { variants })
+            }
+            Data::Struct(data) => {
+                let mut ty_attrs = ty_attrs;
+                if let Some(error_code) = &mut ty_attrs.error_code {
+                    error_code.resolve(&[ident]);
+                }
+                let fields_attrs = FieldsAttrs::new(&data.fields)?;
+                check_delegate(&ty_attrs, &fields_attrs)?;
+                forward_code(&mut ty_attrs, &fields_attrs)?;
+                implicit_errors_code(&mut ty_attrs, &fields_attrs, false);
+                if fields_attrs.delegate.is_some() && ty_attrs.code.is_some() {
+                    return Err(Error::new_spanned(
+                        fields_attrs.delegate.unwrap().kw,
+                        "can't specify both code and delegate",
+                    ));
+                }
+                check_code_with(&ty_attrs, &fields_attrs)?;
+                if fields_attrs.delegate.is_none() && ty_attrs.code.is_none() {
+                    return Err(Error::new_spanned(
+                        repr.ident.clone(),
+                        "code or delegate must be specified",
+                    ));
+                }
+                check_attrs(&ty_attrs, &fields_attrs)?;
+                ItemData::Struct(ItemDataStruct {
+                    ty_attrs,
+                    fields_attrs,
+                    repr: data,
+                })
+            }
+            Data::Union(data) => {
+                let mut ty_attrs = ty_attrs;
+                if let Some(error_code) = &mut ty_attrs.error_code {
+                    error_code.resolve(&[ident]);
+                }
+                forward_code(&mut ty_attrs, &FieldsAttrs::default())?;
+                check_code_with(&ty_attrs, &FieldsAttrs::default())?;
+                if ty_attrs.code.is_none() {
+                    return Err(Error::new_spanned(
+                        repr.ident.clone(),
+                        "code must be specified",
+                    ));
+                }
+                let fields_attrs = FieldsAttrs::new(&data.fields.into())?;
+                if let Some(delegate) = fields_attrs.delegate {
+                    return Err(Error::new_spanned(
+                        delegate.kw,
+                        "can't use delegate on union type",
+                    ));
+                }
+                if let Some(span) = fields_attrs.marker_spans().first() {
+                    return Err(Error::new(
+                        *span,
+                        "can't use field attributes on union type",
+                    ));
+                }
+                check_attrs(&ty_attrs, &fields_attrs)?;
+                ItemData::Union(ItemDataUnion { ty_attrs })
+            }
+        };
+
+        Ok(Self {
+            ident: repr.ident,
+            generics: repr.generics,
+            data,
+        })
+    }
+}
+
+/// Whether responses with `code` carry a body.
+///
+/// Informational, `204 No Content` and `304 Not Modified` responses can't have one, and
+/// redirects that the client must follow with a new request don't need one.
+pub fn has_body(code: u16) -> bool {
+    !matches!(code, 100..=199 | 204 | 303 | 304 | 307)
+}
+
+/// Whether responses with `code` carry an authentication challenge header.
+pub fn is_challenge_code(code: u16) -> bool {
+    code == 401 || code == 407
+}
+
+/// Rejects the arguments that only apply when responding with a code.
+fn check_delegate(attrs: &TypeAttrs, fields_attrs: &FieldsAttrs) -> Result<()> {
+    if fields_attrs.delegate.is_none() {
+        return Ok(());
+    }
+    if let Some(formatter) = &attrs.formatter {
+        return Err(Error::new_spanned(
+            formatter.kw,
+            "can't specify both formatter and delegate",
+        ));
+    }
+    if let Some(error_code) = &attrs.error_code {
+        return Err(Error::new_spanned(
+            error_code.kw,
+            "can't specify both error_code and delegate",
+        ));
+    }
+    if let Some(content_type) = &attrs.content_type {
+        return Err(Error::new_spanned(
+            content_type.kw,
+            "can't specify both content_type and delegate",
+        ));
+    }
+    if let Some(code_with) = &attrs.code_with {
+        return Err(Error::new_spanned(
+            code_with.kw,
+            "can't specify both code_with and delegate",
+        ));
+    }
+    if let Some(forward) = &attrs.forward {
+        return Err(Error::new_spanned(
+            forward.kw,
+            "can't specify both forward and delegate",
+        ));
+    }
+    if let Some(message) = &attrs.message {
+        return Err(Error::new_spanned(
+            message.kw,
+            "can't specify both message and delegate",
+        ));
+    }
+
+    Ok(())
+}
+
+/// Uses the forwarded status as the code, after rejecting the arguments that only apply to the
+/// responses built by the derive.
+fn forward_code(attrs: &mut TypeAttrs, fields_attrs: &FieldsAttrs) -> Result<()> {
+    let forward = match &attrs.forward {
+        Some(forward) => forward,
+        None => return Ok(()),
+    };
+    if attrs.code.is_some() {
+        return Err(Error::new_spanned(
+            forward.kw,
+            "can't specify both code and forward",
+        ));
+    }
+    if let Some(code_with) = &attrs.code_with {
+        return Err(Error::new_spanned(
+            code_with.kw,
+            "can't specify both code_with and forward",
+        ));
+    }
+    if let Some(formatter) = &attrs.formatter {
+        return Err(Error::new_spanned(
+            formatter.kw,
+            "can't specify both formatter and forward",
+        ));
+    }
+    if let Some(content_type) = &attrs.content_type {
+        return Err(Error::new_spanned(
+            content_type.kw,
+            "can't specify both content_type and forward",
+        ));
+    }
+    if let Some(challenge) = &attrs.challenge {
+        return Err(Error::new_spanned(
+            challenge.kw,
+            "can't specify both challenge and forward",
+        ));
+    }
+    if let Some(error_code) = &attrs.error_code {
+        return Err(Error::new_spanned(
+            error_code.kw,
+            "can't specify both error_code and forward",
+        ));
+    }
+    if let Some(span) = fields_attrs.response_marker_spans().first() {
+        return Err(Error::new(
+            *span,
+            "can't use response field attributes together with forward",
+        ));
+    }
+    attrs.code = Some(CodeArg::implicit(forward.code, forward.kw.span));
+    Ok(())
+}
+
+/// Responds to aggregated errors without a code of their own, nor one on their type, with the
+/// most severe status among them, or 422, and to validation errors with 422.
+fn implicit_errors_code(attrs: &mut TypeAttrs, fields_attrs: &FieldsAttrs, type_code: bool) {
+    if attrs.code.is_some() || fields_attrs.delegate.is_some() {
+        return;
+    }
+    if let Some(marker) = &fields_attrs.errors {
+        // the type level code is inherited afterwards
+        if !type_code {
+            attrs.code = Some(CodeArg::implicit(422, marker.kw.span));
+            attrs.status_from_errors = true;
+        }
+    } else if let Some(marker) = &fields_attrs.validation {
+        attrs.code = Some(CodeArg::implicit(422, marker.kw.span));
+    }
+}
+
+/// Rejects `code_with` without a `code` to fall back to.
+fn check_code_with(attrs: &TypeAttrs, fields_attrs: &FieldsAttrs) -> Result<()> {
+    match &attrs.code_with {
+        Some(code_with) if attrs.code.is_none() && fields_attrs.delegate.is_none() => Err(
+            Error::new_spanned(code_with.kw, "code_with requires a code to fall back to"),
+        ),
+        _ => Ok(()),
+    }
+}
+
+/// Rejects error codes shared by several variants of the same enum.
+fn check_error_codes(variants: &[Variant]) -> Result<()> {
+    let mut seen = HashSet::new();
+    for code in variants
+        .iter()
+        .filter_map(|v| v.variant_attrs.error_code.as_ref()?.code.as_ref())
+    {
+        if !seen.insert(code.value()) {
+            return Err(Error::new_spanned(
+                code,
+                format!("duplicate error code `{}`", code.value()),
+            ));
+        }
+    }
+    Ok(())
+}
+
+fn check_attrs(attrs: &TypeAttrs, fields_attrs: &FieldsAttrs) -> Result<()> {
+    if let Some(marker) = &fields_attrs.body {
+        if cfg!(not(feature = "json")) {
+            return Err(Error::new_spanned(
+                marker.kw,
+                "body requires the json feature",
+            ));
+        }
+        if fields_attrs.delegate.is_some() {
+            return Err(Error::new_spanned(
+                marker.kw,
+                "can't specify both body and delegate",
+            ));
+        }
+        if attrs.formatter.is_some() {
+            return Err(Error::new_spanned(
+                marker.kw,
+                "can't specify both body and formatter",
+            ));
+        }
+        if let Some(code) = attrs.code.as_ref().filter(|c| !has_body(c.code)) {
+            return Err(Error::new_spanned(
+                marker.kw,
+                format!("body can't be used with code {}", code.code),
+            ));
+        }
+    }
+    if let Some(marker) = fields_attrs.exposed.first() {
+        if cfg!(not(feature = "json")) {
+            return Err(Error::new_spanned(
+                marker.kw,
+                "expose requires the json feature",
+            ));
+        }
+        if fields_attrs.body.is_some() {
+            return Err(Error::new_spanned(
+                marker.kw,
+                "can't specify both expose and body",
+            ));
+        }
+        if fields_attrs.delegate.is_none() && attrs.formatter.is_none() {
+            return Err(Error::new_spanned(marker.kw, "expose requires a formatter"));
+        }
+        if let Some(code) = attrs.code.as_ref().filter(|c| !has_body(c.code)) {
+            return Err(Error::new_spanned(
+                marker.kw,
+                format!("expose can't be used with code {}", code.code),
+            ));
+        }
+    }
+    if let Some(marker) = &fields_attrs.errors {
+        if fields_attrs.body.is_some() {
+            return Err(Error::new_spanned(
+                marker.kw,
+                "can't specify both errors and body",
+            ));
+        }
+        if let Some(code) = attrs.code.as_ref().filter(|c| !has_body(c.code)) {
+            return Err(Error::new_spanned(
+                marker.kw,
+                format!("errors can't be used with code {}", code.code),
+            ));
+        }
+    }
+    if let Some(marker) = &fields_attrs.validation {
+        if cfg!(not(feature = "validator")) {
+            return Err(Error::new_spanned(
+                marker.kw,
+                "validation requires the validator feature",
+            ));
+        }
+        if fields_attrs.body.is_some() {
+            return Err(Error::new_spanned(
+                marker.kw,
+                "can't specify both validation and body",
+            ));
+        }
+        if fields_attrs.errors.is_some() {
+            return Err(Error::new_spanned(
+                marker.kw,
+                "can't specify both validation and errors",
+            ));
+        }
+        if attrs.formatter.is_some() {
+            return Err(Error::new_spanned(
+                marker.kw,
+                "can't specify both validation and formatter",
+            ));
+        }
+        if attrs.content_type.is_some() {
+            return Err(Error::new_spanned(
+                marker.kw,
+                "can't specify both validation and content_type",
+            ));
+        }
+        if let Some(code) = attrs.code.as_ref().filter(|c| !has_body(c.code)) {
+            return Err(Error::new_spanned(
+                marker.kw,
+                format!("validation can't be used with code {}", code.code),
+            ));
+        }
+    }
+    if fields_attrs.delegate.is_some() {
+        if let Some(span) = fields_attrs.marker_spans().first() {
+            return Err(Error::new(
+                *span,
+                "can't use field attributes together with delegate",
+            ));
+        }
+    }
+    if let Some(marker) = &fields_attrs.location {
+        if !matches!(&attrs.code, Some(c) if (300..=399).contains(&c.code)) {
+            return Err(Error::new_spanned(
+                marker.kw,
+                "location can only be used with a 3xx code",
+            ));
+        }
+    }
+    match &attrs.challenge {
+        Some(challenge) if !matches!(&attrs.code, Some(c) if is_challenge_code(c.code)) => {
+            Err(Error::new_spanned(
+                challenge.kw,
+                "challenge can only be used with code 401 or 407",
+            ))
+        }
+        Some(challenge) => {
+            let static_param = fields_attrs.challenge_params.iter().find(|param| {
+                challenge
+                    .params
+                    .iter()
+                    .any(|(name, _)| name.eq_ignore_ascii_case(&param.name))
+            });
+            match static_param {
+                Some(param) => Err(Error::new_spanned(
+                    param.kw,
+                    format!(
+                        "challenge parameter `{}` is already given by the challenge",
+                        param.name
+                    ),
+                )),
+                None => Ok(()),
+            }
+        }
+        None => match fields_attrs.challenge_params.first() {
+            Some(param) => Err(Error::new_spanned(
+                param.kw,
+                "challenge parameter requires a challenge",
+            )),
+            None => Ok(()),
+        },
+    }
+}
+
+#[allow(clippy::large_enum_variant)]
+pub enum ItemData {
+    Enum(ItemDataEnum),
+    Struct(ItemDataStruct),
+    Union(ItemDataUnion),
+}
+pub struct ItemDataEnum {
+    pub variants: Vec<Variant>,
+}
+
+pub struct ItemDataStruct {
+    pub repr: syn::DataStruct,
+    pub ty_attrs: TypeAttrs,
+    pub fields_attrs: FieldsAttrs,
+}
+
+pub struct ItemDataUnion {
+    pub ty_attrs: TypeAttrs,
+}
+
+pub struct Variant {
+    pub repr: syn::Variant,
+    pub variant_attrs: TypeAttrs,
+    pub fields_attrs: FieldsAttrs,
+}
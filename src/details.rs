@@ -0,0 +1,118 @@
+use std::{cmp::Reverse, fmt::Display};
+
+use rocket::http::Status;
+
+/// Statically known details of an error, implemented by the derive.
+pub trait ErrorDetails {
+    /// The status declared with `code`, or that of the delegate.
+    ///
+    /// Unlike the status of the response, it doesn't take `code_with` or the configured
+    /// overrides into account.
+    fn status(&self) -> Status;
+
+    /// The path of the field the error is about, tagged with `#[response(path)]`.
+    fn path(&self) -> Option<String> {
+        None
+    }
+}
+
+/// An error listed in an aggregated response.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AggregatedError {
+    pub status: Status,
+    /// The `Display` message of the error.
+    pub message: String,
+    pub path: Option<String>,
+}
+
+impl AggregatedError {
+    pub fn new<E: ErrorDetails + Display + ?Sized>(error: &E) -> Self {
+        Self {
+            status: error.status(),
+            message: error.to_string(),
+            path: error.path(),
+        }
+    }
+}
+
+/// The collections of errors a field tagged with `#[response(errors)]` can hold.
+pub trait ErrorList {
+    fn aggregated(&self) -> Vec<AggregatedError>;
+}
+
+impl<E: ErrorDetails + Display> ErrorList for [E] {
+    fn aggregated(&self) -> Vec<AggregatedError> {
+        self.iter().map(AggregatedError::new).collect()
+    }
+}
+
+impl<E: ErrorDetails + Display> ErrorList for Vec<E> {
+    fn aggregated(&self) -> Vec<AggregatedError> {
+        self.as_slice().aggregated()
+    }
+}
+
+impl<T: ErrorList + ?Sized> ErrorList for Box<T> {
+    fn aggregated(&self) -> Vec<AggregatedError> {
+        T::aggregated(self)
+    }
+}
+
+impl<T: ErrorList + ?Sized> ErrorList for &T {
+    fn aggregated(&self) -> Vec<AggregatedError> {
+        T::aggregated(self)
+    }
+}
+
+/// The status of the most severe error among `errors`.
+///
+/// Severity is that of the status class: server errors are more severe than client errors, which
+/// are more severe than any other status. Statuses of the same class aren't ranked, so the first
+/// listed error of the most severe class wins, e.g. `409` for `[409, 422, 400]`.
+pub fn severest(errors: &[AggregatedError]) -> Option<Status> {
+    errors
+        .iter()
+        .map(|e| e.status)
+        .min_by_key(|s| Reverse(s.code / 100))
+}
+
+/// Renders the plain text body of an aggregated response: the message, then each error on its
+/// own line, prefixed with its path if any.
+pub fn plain(message: &str, errors: &[AggregatedError]) -> String {
+    let mut body = message.to_owned();
+    for error in errors {
+        body.push('\n');
+        if let Some(path) = &error.path {
+            body.push_str(path);
+            body.push_str(": ");
+        }
+        body.push_str(&error.message);
+    }
+    body
+}
+
+/// Resolves the status of a delegate, whether or not its type implements `ErrorDetails`.
+///
+/// `(&DelegateStatus(&field)).delegate_status()` picks the `ErrorDetails` implementation when
+/// there is one, through auto-ref, and `None` otherwise.
+pub struct DelegateStatus<'a, T>(pub &'a T);
+
+pub trait ViaErrorDetails {
+    fn delegate_status(&self) -> Option<Status>;
+}
+
+impl<T: ErrorDetails> ViaErrorDetails for DelegateStatus<'_, T> {
+    fn delegate_status(&self) -> Option<Status> {
+        Some(self.0.status())
+    }
+}
+
+pub trait ViaFallback {
+    fn delegate_status(&self) -> Option<Status>;
+}
+
+impl<T> ViaFallback for &DelegateStatus<'_, T> {
+    fn delegate_status(&self) -> Option<Status> {
+        None
+    }
+}
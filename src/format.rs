@@ -0,0 +1,133 @@
+use rocket::{
+    http::{ContentType, Status},
+    Request,
+};
+
+use crate::AggregatedError;
+
+/// Describes the error being turned into a response.
+///
+/// More fields may be added, some of them by features, so it is built with `new`.
+#[derive(Debug)]
+#[non_exhaustive]
+pub struct ErrorInfo<'a> {
+    /// The name of the derived type.
+    pub type_name: &'static str,
+    /// The name of the variant, for enums.
+    pub variant: Option<&'static str>,
+    /// The machine-readable code given with `#[response(error_code = ...)]`.
+    pub error_code: Option<&'static str>,
+    pub status: Status,
+    /// The `message` of the error if any, else its `Display` message, or their configured
+    /// override.
+    pub message: &'a str,
+    /// The errors listed by the field tagged with `#[response(errors)]`, if any.
+    pub errors: Vec<AggregatedError>,
+    /// The fields tagged with `#[response(expose)]`, keyed by their exposed name.
+    #[cfg(feature = "json")]
+    pub exposed: Vec<(&'static str, serde_json::Value)>,
+}
+
+impl<'a> ErrorInfo<'a> {
+    /// Describes an error of the type `type_name`, with no variant, error code, aggregated
+    /// errors nor exposed fields.
+    pub fn new(type_name: &'static str, status: Status, message: &'a str) -> Self {
+        Self {
+            type_name,
+            variant: None,
+            error_code: None,
+            status,
+            message,
+            errors: Vec::new(),
+            #[cfg(feature = "json")]
+            exposed: Vec::new(),
+        }
+    }
+}
+
+/// Builds the body of responses, as selected with `#[response(formatter = MyFormatter)]`.
+///
+/// ```ignore
+/// struct Envelope;
+///
+/// impl ErrorFormatter for Envelope {
+///     fn format(info: &ErrorInfo<'_>, _request: &Request<'_>) -> (ContentType, String) {
+///         (ContentType::HTML, format!("<p>{}</p>", info.message))
+///     }
+/// }
+/// ```
+pub trait ErrorFormatter {
+    /// Returns the content type and the body of the response.
+    fn format(info: &ErrorInfo<'_>, request: &Request<'_>) -> (ContentType, String);
+}
+
+/// Formats errors as a JSON object holding the `message`, the `error_code` if any, the exposed
+/// fields and the aggregated `errors`.
+#[cfg(feature = "json")]
+pub struct Json;
+
+#[cfg(feature = "json")]
+impl ErrorFormatter for Json {
+    fn format(info: &ErrorInfo<'_>, _request: &Request<'_>) -> (ContentType, String) {
+        let mut object = serde_json::Map::new();
+        object.insert("message".into(), info.message.into());
+        if let Some(error_code) = info.error_code {
+            object.insert("error_code".into(), error_code.into());
+        }
+        for (name, value) in &info.exposed {
+            object.insert((*name).into(), value.clone());
+        }
+        if !info.errors.is_empty() {
+            object.insert("errors".into(), errors(&info.errors));
+        }
+        (
+            ContentType::JSON,
+            serde_json::Value::from(object).to_string(),
+        )
+    }
+}
+
+/// Formats errors as RFC 7807 problem details, with the `error_code`, the exposed fields and
+/// the aggregated `errors` as extension members.
+#[cfg(feature = "json")]
+pub struct ProblemJson;
+
+#[cfg(feature = "json")]
+impl ErrorFormatter for ProblemJson {
+    fn format(info: &ErrorInfo<'_>, _request: &Request<'_>) -> (ContentType, String) {
+        let mut object = serde_json::Map::new();
+        object.insert("type".into(), "about:blank".into());
+        object.insert("title".into(), info.status.reason.into());
+        object.insert("status".into(), info.status.code.into());
+        object.insert("detail".into(), info.message.into());
+        if let Some(error_code) = info.error_code {
+            object.insert("error_code".into(), error_code.into());
+        }
+        for (name, value) in &info.exposed {
+            object.insert((*name).into(), value.clone());
+        }
+        if !info.errors.is_empty() {
+            object.insert("errors".into(), errors(&info.errors));
+        }
+        (
+            ContentType::new("application", "problem+json"),
+            serde_json::Value::from(object).to_string(),
+        )
+    }
+}
+
+/// Lists aggregated errors as objects holding their `message` and their `path` if any.
+#[cfg(feature = "json")]
+fn errors(errors: &[AggregatedError]) -> serde_json::Value {
+    errors
+        .iter()
+        .map(|error| {
+            let mut object = serde_json::Map::new();
+            object.insert("message".into(), error.message.as_str().into());
+            if let Some(path) = &error.path {
+                object.insert("path".into(), path.as_str().into());
+            }
+            serde_json::Value::from(object)
+        })
+        .collect()
+}
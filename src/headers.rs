@@ -0,0 +1,136 @@
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use rocket::http::Status;
+
+/// A value that can be rendered into a `Retry-After` header.
+///
+/// A [`Duration`] is rendered in delta-seconds form and a [`SystemTime`] in HTTP-date form.
+/// `None` omits the header.
+pub trait RetryAfter {
+    fn retry_after(&self) -> Option<String>;
+}
+
+impl RetryAfter for Duration {
+    fn retry_after(&self) -> Option<String> {
+        Some(delta_seconds(*self).to_string())
+    }
+}
+
+impl RetryAfter for SystemTime {
+    fn retry_after(&self) -> Option<String> {
+        Some(http_date(*self))
+    }
+}
+
+impl<T: RetryAfter> RetryAfter for Option<T> {
+    fn retry_after(&self) -> Option<String> {
+        self.as_ref().and_then(T::retry_after)
+    }
+}
+
+impl<T: RetryAfter + ?Sized> RetryAfter for &T {
+    fn retry_after(&self) -> Option<String> {
+        (**self).retry_after()
+    }
+}
+
+/// A value that can be rendered into one of the `RateLimit-Limit`, `RateLimit-Remaining` or
+/// `RateLimit-Reset` headers.
+///
+/// Durations and points in time are rendered as the number of seconds from now.
+/// `None` omits the header.
+pub trait RateLimitValue {
+    fn rate_limit_value(&self) -> Option<u64>;
+}
+
+macro_rules! impl_rate_limit_value {
+    ($($ty:ty),*) => {$(
+        impl RateLimitValue for $ty {
+            fn rate_limit_value(&self) -> Option<u64> {
+                Some(*self as u64)
+            }
+        }
+    )*};
+}
+
+impl_rate_limit_value!(u8, u16, u32, u64, usize);
+
+impl RateLimitValue for Duration {
+    fn rate_limit_value(&self) -> Option<u64> {
+        Some(delta_seconds(*self))
+    }
+}
+
+impl RateLimitValue for SystemTime {
+    fn rate_limit_value(&self) -> Option<u64> {
+        let now = SystemTime::now();
+        Some(delta_seconds(self.duration_since(now).unwrap_or_default()))
+    }
+}
+
+impl<T: RateLimitValue> RateLimitValue for Option<T> {
+    fn rate_limit_value(&self) -> Option<u64> {
+        self.as_ref().and_then(T::rate_limit_value)
+    }
+}
+
+impl<T: RateLimitValue + ?Sized> RateLimitValue for &T {
+    fn rate_limit_value(&self) -> Option<u64> {
+        (**self).rate_limit_value()
+    }
+}
+
+/// Rounds up so that clients never retry too early.
+fn delta_seconds(duration: Duration) -> u64 {
+    duration.as_secs() + if duration.subsec_nanos() > 0 { 1 } else { 0 }
+}
+
+/// Formats `time` as an IMF-fixdate, as defined in RFC 7231, section 7.1.1.1.
+fn http_date(time: SystemTime) -> String {
+    const WEEKDAYS: [&str; 7] = ["Thu", "Fri", "Sat", "Sun", "Mon", "Tue", "Wed"];
+    const MONTHS: [&str; 12] = [
+        "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+    ];
+
+    let secs = time
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    let days = secs / 86400;
+    let secs_of_day = secs % 86400;
+
+    // converts days since the epoch to a civil date, see
+    // http://howardhinnant.github.io/date_algorithms.html#civil_from_days
+    let z = days + 719_468;
+    let era = z / 146_097;
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+
+    format!(
+        "{}, {:02} {} {:04} {:02}:{:02}:{:02} GMT",
+        WEEKDAYS[(days % 7) as usize],
+        day,
+        MONTHS[(month - 1) as usize],
+        year,
+        secs_of_day / 3600,
+        secs_of_day % 3600 / 60,
+        secs_of_day % 60,
+    )
+}
+
+/// The header carrying the authentication challenge of responses with `status`, if any.
+///
+/// The status may differ from the code the challenge was declared with, when resolved by
+/// `code_with` or overridden through the configuration.
+pub fn challenge_header(status: Status) -> Option<&'static str> {
+    match status.code {
+        401 => Some("WWW-Authenticate"),
+        407 => Some("Proxy-Authenticate"),
+        _ => None,
+    }
+}
@@ -0,0 +1,102 @@
+use std::{
+    fmt::{self, Debug, Display},
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+use rocket::{http::Status, response, Request};
+
+/// Displays the method, URI, matched route and client IP of a request, for log lines.
+pub struct RequestContext<'a, 'r>(pub &'a Request<'r>);
+
+impl fmt::Display for RequestContext<'_, '_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let request = self.0;
+        write!(f, "{} {} (route: ", request.method(), request.uri())?;
+        match request.route().and_then(|route| route.name) {
+            Some(name) => f.write_str(name)?,
+            None => f.write_str("-")?,
+        }
+        f.write_str(", client: ")?;
+        match request.client_ip() {
+            Some(ip) => write!(f, "{})", ip),
+            None => f.write_str("-)"),
+        }
+    }
+}
+
+/// The levels at which responses are logged.
+pub enum Level {
+    Debug,
+    Info,
+    Warn,
+    Error,
+}
+
+impl Level {
+    /// The level of responses with `status` when none is specified: `Info` for 2xx and 3xx,
+    /// `Warn` for 4xx and `Error` for 5xx. Other responses aren't logged.
+    pub fn of(status: Status) -> Option<Self> {
+        match status.code {
+            200..=399 => Some(Level::Info),
+            400..=499 => Some(Level::Warn),
+            500..=599 => Some(Level::Error),
+            _ => None,
+        }
+    }
+}
+
+/// Whether the response being built is logged by a delegating type, in the request-local cache.
+struct Delegated(AtomicBool);
+
+fn delegated_flag<'a>(request: &'a Request<'_>) -> &'a AtomicBool {
+    &request.local_cache(|| Delegated(AtomicBool::new(false))).0
+}
+
+/// Builds the response of a delegate with its logging turned off, as the delegating type logs
+/// it at its own level.
+pub fn delegate<'o>(
+    request: &Request<'_>,
+    respond: impl FnOnce() -> response::Result<'o>,
+) -> response::Result<'o> {
+    let flag = delegated_flag(request);
+    let delegated = flag.swap(true, Ordering::Relaxed);
+    let response = respond();
+    flag.store(delegated, Ordering::Relaxed);
+    response
+}
+
+/// Logs a response at `level`, if any: a line with the request context and `message`, then one
+/// with the `Debug` representation of `error`. Nothing is logged within `delegate`.
+pub fn log(
+    request: &Request<'_>,
+    level: impl Into<Option<Level>>,
+    message: &dyn Display,
+    error: &dyn Debug,
+) {
+    let level = match level.into() {
+        Some(level) => level,
+        None => return,
+    };
+    if delegated_flag(request).load(Ordering::Relaxed) {
+        return;
+    }
+    let context = RequestContext(request);
+    match level {
+        Level::Debug => {
+            rocket::debug!("{}: {}", context, message);
+            rocket::debug!("{:?}", error);
+        }
+        Level::Info => {
+            rocket::info!("{}: {}", context, message);
+            rocket::info!("{:?}", error);
+        }
+        Level::Warn => {
+            rocket::warn!("{}: {}", context, message);
+            rocket::warn!("{:?}", error);
+        }
+        Level::Error => {
+            rocket::error!("{}: {}", context, message);
+            rocket::error!("{:?}", error);
+        }
+    }
+}
@@ -0,0 +1,55 @@
+use std::sync::Mutex;
+
+use rocket::{
+    http::Status,
+    response::{self, Responder},
+    Request, Response,
+};
+
+/// The slot of the local cache holding the error of type `E`.
+struct CachedSlot<E>(Mutex<Option<E>>);
+
+fn slot<'a, E: Send + 'static>(request: &'a Request<'_>) -> &'a CachedSlot<E> {
+    request.local_cache(|| CachedSlot(Mutex::new(None)))
+}
+
+/// Stores `error` in the local cache of `request`, for a catcher to respond with through
+/// `CachedError`.
+///
+/// A later call for the same type replaces the cached error.
+pub fn cache_error<E: Send + 'static>(request: &Request<'_>, error: E) {
+    *slot(request).0.lock().unwrap() = Some(error);
+}
+
+/// Responds with the error of type `E` cached for the request by `cache_error`, or else with an
+/// empty response with the fallback status.
+///
+/// ```ignore
+/// #[catch(401)]
+/// fn unauthorized(request: &Request<'_>) -> CachedError<AuthError> {
+///     CachedError::new(request, Status::Unauthorized)
+/// }
+/// ```
+pub struct CachedError<E> {
+    error: Option<E>,
+    fallback: Status,
+}
+
+impl<E: Send + 'static> CachedError<E> {
+    /// Takes the error out of the local cache of `request`.
+    pub fn new(request: &Request<'_>, fallback: Status) -> Self {
+        Self {
+            error: slot(request).0.lock().unwrap().take(),
+            fallback,
+        }
+    }
+}
+
+impl<'r, 'o: 'r, E: Responder<'r, 'o>> Responder<'r, 'o> for CachedError<E> {
+    fn respond_to(self, request: &'r Request<'_>) -> response::Result<'o> {
+        match self.error {
+            Some(error) => error.respond_to(request),
+            None => Response::build().status(self.fallback).ok(),
+        }
+    }
+}
@@ -0,0 +1,110 @@
+use std::collections::HashMap;
+
+use rocket::{
+    fairing::{AdHoc, Fairing},
+    figment::{self, value::Value, Figment},
+    http::Status,
+    Request,
+};
+
+/// The `error_overrides` table of Rocket's configuration, read once instead of on every response.
+///
+/// ```ignore
+/// rocket::ignite().attach(ErrorOverrides::fairing());
+/// ```
+///
+/// Without the fairing, the override of an error is read from the configuration each time it
+/// is turned into a response.
+#[derive(Debug, Default)]
+pub struct ErrorOverrides {
+    overrides: HashMap<String, Override>,
+}
+
+#[derive(Debug)]
+struct Override {
+    status: Option<Status>,
+    message: Option<String>,
+}
+
+impl ErrorOverrides {
+    /// Reads the table from `figment`, logging the entries that can't be used.
+    pub fn from_figment(figment: &Figment) -> Self {
+        let table = figment.extract_inner::<HashMap<String, Value>>("error_overrides");
+        let table = usable("error_overrides", table).unwrap_or_default();
+        let overrides = table
+            .iter()
+            .filter_map(|(key, value)| {
+                if !matches!(value, Value::Dict(..)) {
+                    rocket::warn!(
+                        "ignoring the error override of `{}`, which isn't a table",
+                        key
+                    );
+                    return None;
+                }
+                let value = Override {
+                    status: status(figment, key),
+                    message: message(figment, key),
+                };
+                Some((key.clone(), value))
+            })
+            .collect();
+        Self { overrides }
+    }
+
+    /// A fairing managing the table read from Rocket's configuration.
+    pub fn fairing() -> impl Fairing {
+        AdHoc::on_attach("Error overrides", |rocket| async move {
+            let overrides = Self::from_figment(rocket.figment());
+            Ok(rocket.manage(overrides))
+        })
+    }
+}
+
+/// The status `key` is overridden with, from the managed `ErrorOverrides` if any, or else from
+/// the configuration.
+pub(crate) fn lookup_status(request: &Request<'_>, key: &str) -> Option<Status> {
+    match request.rocket().state::<ErrorOverrides>() {
+        Some(overrides) => overrides.overrides.get(key)?.status,
+        None => status(request.rocket().figment(), key),
+    }
+}
+
+/// The message `key` is overridden with, from the managed `ErrorOverrides` if any, or else from
+/// the configuration.
+pub(crate) fn lookup_message(request: &Request<'_>, key: &str) -> Option<String> {
+    match request.rocket().state::<ErrorOverrides>() {
+        Some(overrides) => overrides.overrides.get(key)?.message.clone(),
+        None => message(request.rocket().figment(), key),
+    }
+}
+
+fn status(figment: &Figment, key: &str) -> Option<Status> {
+    let path = format!("error_overrides.{}.code", key);
+    let code = usable(&path, figment.extract_inner::<u16>(&path))?;
+    let status = Status::from_code(code);
+    if status.is_none() {
+        rocket::warn!(
+            "ignoring the code of the error override of `{}`: {} isn't a status code",
+            key,
+            code
+        );
+    }
+    status
+}
+
+fn message(figment: &Figment, key: &str) -> Option<String> {
+    let path = format!("error_overrides.{}.message", key);
+    usable(&path, figment.extract_inner::<String>(&path))
+}
+
+/// The value extracted at `path`, or `None` if it is missing or can't be used, which is logged.
+fn usable<T>(path: &str, value: Result<T, figment::Error>) -> Option<T> {
+    match value {
+        Ok(value) => Some(value),
+        Err(error) if error.missing() => None,
+        Err(error) => {
+            rocket::warn!("ignoring the error override at `{}`: {}", path, error);
+            None
+        }
+    }
+}
@@ -0,0 +1,41 @@
+use std::fmt;
+
+/// The placeholder replacing sensitive values.
+pub const REDACTED: &str = "***";
+
+/// Formats as `***`, for both `Debug` and `Display`, in place of the wrapped sensitive value.
+///
+/// ```ignore
+/// impl fmt::Debug for Credentials {
+///     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+///         f.debug_struct("Credentials")
+///             .field("user", &self.user)
+///             .field("password", &Redacted(&self.password))
+///             .finish()
+///     }
+/// }
+/// ```
+#[derive(Clone, Copy)]
+pub struct Redacted<T>(pub T);
+
+impl<T> fmt::Debug for Redacted<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(REDACTED)
+    }
+}
+
+impl<T> fmt::Display for Redacted<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(REDACTED)
+    }
+}
+
+/// Formats with the wrapped function, used by the derive to render a `Debug` representation
+/// with the sensitive fields redacted.
+pub struct DebugFn<F>(pub F);
+
+impl<F: Fn(&mut fmt::Formatter<'_>) -> fmt::Result> fmt::Debug for DebugFn<F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        (self.0)(f)
+    }
+}
@@ -0,0 +1,90 @@
+//! A registry of every type deriving `SimpleResponder` linked into the binary.
+//!
+//! ```ignore
+//! for error in rocket_simple_responder::registry::iter() {
+//!     println!("{}::{}", error.module_path, error.type_name);
+//! }
+//! ```
+use std::{collections::BTreeMap, error::Error, fmt};
+
+/// Describes a derived type.
+#[derive(Debug)]
+pub struct ErrorDescriptor {
+    /// The name of the derived type.
+    pub type_name: &'static str,
+    /// The module where the type is defined.
+    pub module_path: &'static str,
+    /// The variants of enums, or a single unnamed variant for structs and unions.
+    pub variants: &'static [VariantDescriptor],
+}
+
+/// Describes a variant of a derived type.
+#[derive(Debug)]
+pub struct VariantDescriptor {
+    /// The name of the variant, for enums.
+    pub name: Option<&'static str>,
+    /// The status code given with `#[response(code = ...)]`, unless the variant delegates.
+    pub code: Option<u16>,
+    /// The machine-readable code given with `#[response(error_code = ...)]`.
+    pub error_code: Option<&'static str>,
+    /// The type of the `delegate` field, as written in the source.
+    pub delegate: Option<&'static str>,
+}
+
+inventory::collect!(ErrorDescriptor);
+
+/// Iterates over the registered types, in no particular order.
+pub fn iter() -> impl Iterator<Item = &'static ErrorDescriptor> {
+    inventory::iter::<ErrorDescriptor>.into_iter()
+}
+
+/// An error code used by several variants, possibly from different crates.
+#[derive(Debug)]
+pub struct DuplicateErrorCode {
+    pub error_code: &'static str,
+    /// The paths of the variants using the code.
+    pub variants: Vec<String>,
+}
+
+impl fmt::Display for DuplicateErrorCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "error code `{}` is used by {}",
+            self.error_code,
+            self.variants.join(", ")
+        )
+    }
+}
+
+impl Error for DuplicateErrorCode {}
+
+/// Returns the error codes shared by several variants of the registered types.
+///
+/// Duplicates within a single enum are rejected at compile time, but those across types or
+/// crates can only be found once the binary is linked, typically when starting it.
+pub fn duplicate_error_codes() -> Vec<DuplicateErrorCode> {
+    let mut variants = BTreeMap::<&'static str, Vec<String>>::new();
+    for error in iter() {
+        for variant in error.variants {
+            if let Some(error_code) = variant.error_code {
+                let path = match variant.name {
+                    Some(name) => format!("{}::{}::{}", error.module_path, error.type_name, name),
+                    None => format!("{}::{}", error.module_path, error.type_name),
+                };
+                variants.entry(error_code).or_default().push(path);
+            }
+        }
+    }
+    variants
+        .into_iter()
+        .filter(|(_, variants)| variants.len() > 1)
+        .map(|(error_code, mut variants)| {
+            variants.sort();
+            DuplicateErrorCode {
+                error_code,
+                variants,
+            }
+        })
+        .collect()
+}
@@ -0,0 +1,116 @@
+use std::{
+    error::Error,
+    sync::{Arc, Mutex},
+};
+
+use rocket::{
+    http::{Status, StatusClass},
+    Request,
+};
+
+/// Receives the errors turned into responses, for example to send them to an error tracker.
+pub trait ErrorReporter: Send + Sync + 'static {
+    fn report(&self, error: &dyn Error, request: &Request<'_>, status: Status);
+}
+
+/// The reporter looked up in Rocket's managed state, along with the status classes it receives.
+///
+/// ```ignore
+/// rocket::ignite().manage(Reporter::new(MyTracker));
+/// ```
+pub struct Reporter {
+    reporter: Box<dyn ErrorReporter>,
+    classes: Vec<StatusClass>,
+}
+
+impl Reporter {
+    /// Reports the server errors to `reporter`.
+    pub fn new<R: ErrorReporter>(reporter: R) -> Self {
+        Self {
+            reporter: Box::new(reporter),
+            classes: vec![StatusClass::ServerError],
+        }
+    }
+
+    /// Replaces the status classes being reported.
+    pub fn classes(mut self, classes: &[StatusClass]) -> Self {
+        self.classes = classes.to_vec();
+        self
+    }
+
+    /// Reports `error` if `status` belongs to one of the configured classes.
+    pub fn report(&self, error: &dyn Error, request: &Request<'_>, status: Status) {
+        if self.classes.contains(&status.class()) {
+            self.reporter.report(error, request, status);
+        }
+    }
+}
+
+/// Reports `error` to the `Reporter` in Rocket's managed state, if any.
+fn report(request: &Request<'_>, error: &dyn Error, status: Status) {
+    if let Some(reporter) = request.rocket().state::<Reporter>() {
+        reporter.report(error, request, status);
+    }
+}
+
+/// Reports an error if its type implements `Error`, and skips it otherwise.
+///
+/// `(&Reportable(&error)).report(request, status)` picks the `Error` implementation when there
+/// is one, through auto-ref, and does nothing otherwise.
+pub struct Reportable<'a, T>(pub &'a T);
+
+pub trait ViaError {
+    fn report(&self, request: &Request<'_>, status: Status);
+}
+
+impl<T: Error> ViaError for Reportable<'_, T> {
+    fn report(&self, request: &Request<'_>, status: Status) {
+        report(request, self.0, status);
+    }
+}
+
+pub trait ViaNoError {
+    fn report(&self, request: &Request<'_>, status: Status);
+}
+
+impl<T> ViaNoError for &Reportable<'_, T> {
+    fn report(&self, _request: &Request<'_>, _status: Status) {}
+}
+
+/// An error received by a `MemoryReporter`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Report {
+    pub status: Status,
+    /// The `Display` message of the error.
+    pub message: String,
+    pub uri: String,
+}
+
+/// Keeps the reported errors in memory, for tests.
+///
+/// Clones share the same reports, so one can be kept to inspect what the managed one received.
+#[derive(Debug, Clone, Default)]
+pub struct MemoryReporter {
+    reports: Arc<Mutex<Vec<Report>>>,
+}
+
+impl MemoryReporter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the errors reported so far.
+    pub fn reports(&self) -> Vec<Report> {
+        self.reports.lock().unwrap().clone()
+    }
+}
+
+impl ErrorReporter for MemoryReporter {
+    fn report(&self, error: &dyn Error, request: &Request<'_>, status: Status) {
+        self.reports.lock().unwrap().push(Report {
+            status,
+            message: error.to_string(),
+            uri: request.uri().to_string(),
+        });
+    }
+}
@@ -0,0 +1,67 @@
+use std::{borrow::Cow, fmt::Display, io::Cursor};
+
+use rocket::{
+    http::{ContentType, Status},
+    response, Request, Response,
+};
+
+use crate::overrides;
+
+/// A body built by the derived `Responder`, borrowed when static.
+pub type Body = Cow<'static, [u8]>;
+
+/// Turns a message or a formatted body into a `Body`, without copying it.
+pub fn into_body(body: impl Into<Cow<'static, str>>) -> Body {
+    match body.into() {
+        Cow::Borrowed(body) => Cow::Borrowed(body.as_bytes()),
+        Cow::Owned(body) => Cow::Owned(body.into_bytes()),
+    }
+}
+
+/// Resolves the status of a response, overridden for `key` in the `error_overrides` table.
+pub fn override_status(request: &Request<'_>, key: &str, status: &dyn Fn() -> Status) -> Status {
+    overrides::lookup_status(request, key).unwrap_or_else(status)
+}
+
+/// Resolves the message of a response, overridden for `key` in the `error_overrides` table.
+///
+/// The message is `static_message` if any, which is borrowed, and else the `Display` message
+/// of `error`.
+pub fn override_message(
+    request: &Request<'_>,
+    key: &str,
+    static_message: Option<&'static str>,
+    error: &dyn Display,
+) -> Cow<'static, str> {
+    match overrides::lookup_message(request, key) {
+        Some(message) => Cow::Owned(message),
+        None => match static_message {
+            Some(message) => Cow::Borrowed(message),
+            None => Cow::Owned(error.to_string()),
+        },
+    }
+}
+
+/// Builds a response with `status`, `headers` and `body`.
+///
+/// The body is left out for statuses that can't carry one, which may be set through the
+/// configuration. Rocket strips it from the responses to `HEAD` requests.
+pub fn respond<'o>(
+    status: Status,
+    headers: Vec<(&'static str, Cow<'static, str>)>,
+    body: Option<(ContentType, Body)>,
+) -> response::Result<'o> {
+    let mut response = Response::build();
+    response.status(status);
+    for (name, value) in headers {
+        response.raw_header(name, value);
+    }
+    match body {
+        Some((content_type, body)) if !matches!(status.code, 100..=199 | 204 | 304) => {
+            response.header(content_type);
+            response.sized_body(body.len(), Cursor::new(body));
+        }
+        _ => {}
+    }
+    Ok(response.finalize())
+}
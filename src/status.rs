@@ -0,0 +1,42 @@
+use rocket::{http::Status, Request};
+
+/// A function resolving the status of an error at runtime, as selected with
+/// `#[response(code_with = path::to::function)]`.
+///
+/// It is implemented for functions taking either `&E` alone or `&E` and the `&Request`:
+///
+/// ```ignore
+/// fn db_status(error: &Error) -> Status {
+///     match error {
+///         Error::Db(sqlx::Error::RowNotFound) => Status::NotFound,
+///         _ => Status::InternalServerError,
+///     }
+/// }
+/// ```
+pub trait StatusFn<E: ?Sized, Args> {
+    fn status(&self, error: &E, request: &Request<'_>) -> Status;
+}
+
+/// The arguments of functions taking the error alone.
+pub struct ErrorArgs;
+
+/// The arguments of functions taking the error and the request.
+pub struct ErrorRequestArgs;
+
+impl<E: ?Sized, F> StatusFn<E, ErrorArgs> for F
+where
+    F: Fn(&E) -> Status,
+{
+    fn status(&self, error: &E, _request: &Request<'_>) -> Status {
+        self(error)
+    }
+}
+
+impl<E: ?Sized, F> StatusFn<E, ErrorRequestArgs> for F
+where
+    F: Fn(&E, &Request<'_>) -> Status,
+{
+    fn status(&self, error: &E, request: &Request<'_>) -> Status {
+        self(error, request)
+    }
+}
@@ -0,0 +1,113 @@
+//! Helpers for asserting the responses of derived types without mounting routes.
+
+use rocket::{
+    http::{ContentType, Status},
+    local::asynchronous::Client,
+    response::Responder,
+    Rocket,
+};
+
+/// Implemented by the derive, when the `testing` feature is enabled, to list the values given
+/// by `#[response(example = ...)]`.
+pub trait Examples: Sized {
+    /// The examples, keyed by the type name, followed by `::<variant>` for enum variants.
+    fn examples() -> Vec<(&'static str, Self)>;
+}
+
+/// The parts of a response that tests usually care about.
+#[derive(Debug)]
+pub struct TestResponse {
+    pub status: Status,
+    pub content_type: Option<ContentType>,
+    pub headers: Vec<(String, String)>,
+    pub body: String,
+}
+
+impl TestResponse {
+    /// The first value of the header `name`, compared case-insensitively.
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(n, _)| n.eq_ignore_ascii_case(name))
+            .map(|(_, v)| v.as_str())
+    }
+}
+
+/// Responds to a `GET /` request with `value`, using a default Rocket instance.
+pub async fn respond<T>(value: T) -> TestResponse
+where
+    T: for<'r> Responder<'r, 'static>,
+{
+    respond_with(rocket::ignite(), value).await
+}
+
+/// Responds to a `GET /` request with `value`, using `rocket` for configuration and managed
+/// state.
+pub async fn respond_with<T>(rocket: Rocket, value: T) -> TestResponse
+where
+    T: for<'r> Responder<'r, 'static>,
+{
+    let client = Client::untracked(rocket)
+        .await
+        .expect("valid rocket instance");
+    let request = client.get("/");
+    let mut response = match value.respond_to(request.inner()) {
+        Ok(response) => response,
+        Err(status) => {
+            return TestResponse {
+                status,
+                content_type: None,
+                headers: Vec::new(),
+                body: String::new(),
+            }
+        }
+    };
+    TestResponse {
+        status: response.status(),
+        content_type: response.content_type(),
+        headers: response
+            .headers()
+            .iter()
+            .map(|h| (h.name().to_string(), h.value().to_string()))
+            .collect(),
+        body: response
+            .body_mut()
+            .to_string()
+            .await
+            .expect("readable response body"),
+    }
+}
+
+/// Responds with every example of `T`, see [`respond`].
+pub async fn respond_examples<T>() -> Vec<(&'static str, TestResponse)>
+where
+    T: Examples + for<'r> Responder<'r, 'static>,
+{
+    let mut ret = Vec::new();
+    for (key, value) in T::examples() {
+        ret.push((key, respond(value).await));
+    }
+    ret
+}
+
+/// Asserts that a value responds with the given status code and, optionally, body.
+///
+/// Evaluates to the [`TestResponse`](crate::testing::TestResponse) so that further assertions can be
+/// made on it. Must be used in an async context.
+///
+/// ```ignore
+/// assert_responds!(Error::NotFound, 404, "not found");
+/// ```
+#[macro_export]
+macro_rules! assert_responds {
+    ($value:expr, $code:expr $(,)?) => {{
+        let response = $crate::testing::respond($value).await;
+        assert_eq!(response.status.code, $code, "unexpected status code");
+        response
+    }};
+    ($value:expr, $code:expr, $body:expr $(,)?) => {{
+        let response = $crate::assert_responds!($value, $code);
+        assert_eq!(response.body, $body, "unexpected body");
+        response
+    }};
+}
@@ -0,0 +1,79 @@
+use validator_crate::{ValidationErrors, ValidationErrorsKind};
+
+/// A failed validation of a field, as listed in the body of responses to `ValidationErrors`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldViolation {
+    /// The path of the field, such as `address.city` or `items[0].name`.
+    pub path: String,
+    /// The code of the failed validation, such as `length`.
+    pub code: String,
+    /// The message given to the validation, if any.
+    pub message: Option<String>,
+}
+
+/// Lists the failed validations in `errors`, including those of nested structs and lists.
+///
+/// Fields are listed in alphabetical order and list items by index, so that the order doesn't
+/// change between responses.
+pub fn field_violations(errors: &ValidationErrors) -> Vec<FieldViolation> {
+    let mut violations = Vec::new();
+    push_violations(&mut violations, "", errors);
+    violations
+}
+
+fn push_violations(violations: &mut Vec<FieldViolation>, prefix: &str, errors: &ValidationErrors) {
+    let mut fields: Vec<_> = errors.errors().iter().collect();
+    fields.sort_by_key(|(field, _)| *field);
+    for (field, kind) in fields {
+        let path = if prefix.is_empty() {
+            (*field).to_owned()
+        } else {
+            format!("{}.{}", prefix, field)
+        };
+        match kind {
+            ValidationErrorsKind::Field(errors) => {
+                violations.extend(errors.iter().map(|error| FieldViolation {
+                    path: path.clone(),
+                    code: error.code.to_string(),
+                    message: error.message.as_ref().map(|m| m.to_string()),
+                }))
+            }
+            ValidationErrorsKind::Struct(errors) => push_violations(violations, &path, errors),
+            ValidationErrorsKind::List(errors) => {
+                for (index, errors) in errors {
+                    push_violations(violations, &format!("{}[{}]", path, index), errors);
+                }
+            }
+        }
+    }
+}
+
+/// Builds the JSON body of responses to `errors`:
+///
+/// ```json
+/// {
+///     "message": "invalid input",
+///     "errors": [
+///         { "path": "address.city", "code": "length", "message": "too long" },
+///         { "path": "email", "code": "email" }
+///     ]
+/// }
+/// ```
+pub fn body(message: &str, errors: &ValidationErrors) -> String {
+    let violations: Vec<serde_json::Value> = field_violations(errors)
+        .into_iter()
+        .map(|violation| {
+            let mut object = serde_json::Map::new();
+            object.insert("path".into(), violation.path.into());
+            object.insert("code".into(), violation.code.into());
+            if let Some(message) = violation.message {
+                object.insert("message".into(), message.into());
+            }
+            serde_json::Value::from(object)
+        })
+        .collect();
+    let mut object = serde_json::Map::new();
+    object.insert("message".into(), message.into());
+    object.insert("errors".into(), violations.into());
+    serde_json::Value::from(object).to_string()
+}
@@ -0,0 +1,380 @@
+use rocket::{
+    get,
+    http::{ContentType, Status},
+    local::asynchronous::Client,
+    routes,
+};
+use rocket_simple_responder::{ErrorFormatter, ErrorInfo, Json, SimpleResponder};
+use serde::Serialize;
+use thiserror::Error;
+
+#[derive(Debug, Serialize)]
+struct FieldError {
+    field: &'static str,
+    message: &'static str,
+}
+
+#[derive(Debug, Error, SimpleResponder)]
+enum Error {
+    #[error("invalid input")]
+    #[response(code = 422)]
+    Invalid(#[response(body)] Vec<FieldError>),
+    #[error("not found")]
+    #[response(code = 404)]
+    NotFound,
+}
+
+#[get("/")]
+fn case1_route() -> Error {
+    Error::Invalid(vec![FieldError {
+        field: "name",
+        message: "must not be empty",
+    }])
+}
+
+#[tokio::test]
+async fn case1() {
+    let rocket = rocket::ignite().mount("/", routes![case1_route]);
+    let client = Client::untracked(rocket)
+        .await
+        .expect("valid rocket instance");
+    let response = client.get("/").dispatch().await;
+    assert_eq!(response.status(), Status::UnprocessableEntity);
+    assert_eq!(response.content_type(), Some(ContentType::JSON));
+
+    assert_eq!(
+        response.into_string().await,
+        Some(r#"[{"field":"name","message":"must not be empty"}]"#.into())
+    );
+}
+
+#[get("/")]
+fn case2_route() -> Error {
+    Error::NotFound
+}
+
+#[tokio::test]
+async fn case2() {
+    let rocket = rocket::ignite().mount("/", routes![case2_route]);
+    let client = Client::untracked(rocket)
+        .await
+        .expect("valid rocket instance");
+    let response = client.get("/").dispatch().await;
+    assert_eq!(response.status(), Status::NotFound);
+    assert_eq!(response.content_type(), Some(ContentType::Plain));
+}
+
+#[derive(Debug, Error, SimpleResponder)]
+#[response(formatter = rocket_simple_responder::ProblemJson)]
+enum ResourceError {
+    #[error("resource not found")]
+    #[response(code = 404)]
+    NotFound {
+        #[response(expose)]
+        resource_id: u64,
+        owner: String,
+    },
+    #[error("quota exceeded")]
+    #[response(
+        code = 429,
+        formatter = rocket_simple_responder::Json,
+        error_code = "QUOTA_EXCEEDED"
+    )]
+    QuotaExceeded(#[response(expose = "limit")] u32),
+}
+
+#[get("/")]
+fn case3_route() -> ResourceError {
+    ResourceError::NotFound {
+        resource_id: 7,
+        owner: "someone".into(),
+    }
+}
+
+#[tokio::test]
+async fn case3() {
+    let rocket = rocket::ignite().mount("/", routes![case3_route]);
+    let client = Client::untracked(rocket)
+        .await
+        .expect("valid rocket instance");
+    let response = client.get("/").dispatch().await;
+    assert_eq!(response.status(), Status::NotFound);
+    assert_eq!(
+        response.content_type(),
+        Some(ContentType::new("application", "problem+json"))
+    );
+
+    let body: serde_json::Value =
+        serde_json::from_str(&response.into_string().await.unwrap()).unwrap();
+    assert_eq!(
+        body,
+        serde_json::json!({
+            "type": "about:blank",
+            "title": "Not Found",
+            "status": 404,
+            "detail": "resource not found",
+            "resource_id": 7,
+        })
+    );
+}
+
+#[get("/")]
+fn case4_route() -> ResourceError {
+    ResourceError::QuotaExceeded(100)
+}
+
+#[tokio::test]
+async fn case4() {
+    let rocket = rocket::ignite().mount("/", routes![case4_route]);
+    let client = Client::untracked(rocket)
+        .await
+        .expect("valid rocket instance");
+    let response = client.get("/").dispatch().await;
+    assert_eq!(response.status(), Status::TooManyRequests);
+    assert_eq!(response.content_type(), Some(ContentType::JSON));
+
+    assert_eq!(
+        response.into_string().await,
+        Some(r#"{"error_code":"QUOTA_EXCEEDED","limit":100,"message":"quota exceeded"}"#.into())
+    );
+}
+
+#[derive(Debug, Error, SimpleResponder)]
+#[error("account locked")]
+#[response(code = 423, formatter = rocket_simple_responder::Json)]
+struct AccountLocked {
+    #[response(expose, sensitive)]
+    email: String,
+    #[response(expose)]
+    attempts: u32,
+}
+
+#[get("/")]
+fn case5_route() -> AccountLocked {
+    AccountLocked {
+        email: "user@example.com".into(),
+        attempts: 5,
+    }
+}
+
+#[tokio::test]
+async fn case5() {
+    let rocket = rocket::ignite().mount("/", routes![case5_route]);
+    let client = Client::untracked(rocket)
+        .await
+        .expect("valid rocket instance");
+    let response = client.get("/").dispatch().await;
+    assert_eq!(response.status(), Status::Locked);
+
+    assert_eq!(
+        response.into_string().await,
+        Some(r#"{"attempts":5,"email":"***","message":"account locked"}"#.into())
+    );
+}
+
+#[derive(Debug, Error, SimpleResponder)]
+#[error("invalid token {token} for {email}")]
+#[response(code = 401, formatter = rocket_simple_responder::Json)]
+struct InvalidToken {
+    #[response(sensitive)]
+    token: String,
+    #[response(sensitive, expose)]
+    email: String,
+}
+
+#[get("/")]
+fn case12_route() -> InvalidToken {
+    InvalidToken {
+        token: "s3cr3t".into(),
+        email: "user@example.com".into(),
+    }
+}
+
+#[tokio::test]
+async fn case12() {
+    let rocket = rocket::ignite().mount("/", routes![case12_route]);
+    let client = Client::untracked(rocket)
+        .await
+        .expect("valid rocket instance");
+    let response = client.get("/").dispatch().await;
+    assert_eq!(response.status(), Status::Unauthorized);
+    assert_eq!(
+        response.into_string().await,
+        Some(r#"{"email":"***","message":"***"}"#.into())
+    );
+}
+
+#[derive(Debug, Error, SimpleResponder)]
+enum FormError {
+    #[error("must not be empty")]
+    #[response(code = 422)]
+    Empty(#[response(path)] &'static str),
+    #[error("already taken")]
+    #[response(code = 409)]
+    Taken {
+        #[response(path)]
+        field: &'static str,
+    },
+}
+
+#[derive(Debug, Error, SimpleResponder)]
+enum SignupError {
+    #[error("invalid form")]
+    Invalid(#[response(errors)] Vec<FormError>),
+    #[error("invalid form")]
+    #[response(code = 400, formatter = rocket_simple_responder::Json)]
+    InvalidJson(#[response(errors)] Vec<FormError>),
+}
+
+#[get("/")]
+fn case6_route() -> SignupError {
+    SignupError::Invalid(vec![FormError::Empty("name")])
+}
+
+#[tokio::test]
+async fn case6() {
+    let rocket = rocket::ignite().mount("/", routes![case6_route]);
+    let client = Client::untracked(rocket)
+        .await
+        .expect("valid rocket instance");
+    let response = client.get("/").dispatch().await;
+    assert_eq!(response.status(), Status::UnprocessableEntity);
+    assert_eq!(response.content_type(), Some(ContentType::Plain));
+
+    assert_eq!(
+        response.into_string().await,
+        Some("invalid form\nname: must not be empty".into())
+    );
+}
+
+#[get("/")]
+fn case7_route() -> SignupError {
+    SignupError::Invalid(vec![
+        FormError::Taken { field: "email" },
+        FormError::Empty("name"),
+    ])
+}
+
+#[tokio::test]
+async fn case7() {
+    let rocket = rocket::ignite().mount("/", routes![case7_route]);
+    let client = Client::untracked(rocket)
+        .await
+        .expect("valid rocket instance");
+    let response = client.get("/").dispatch().await;
+    assert_eq!(response.status(), Status::Conflict);
+}
+
+#[get("/")]
+fn case8_route() -> SignupError {
+    SignupError::InvalidJson(vec![
+        FormError::Empty("name"),
+        FormError::Taken { field: "email" },
+    ])
+}
+
+#[tokio::test]
+async fn case8() {
+    let rocket = rocket::ignite().mount("/", routes![case8_route]);
+    let client = Client::untracked(rocket)
+        .await
+        .expect("valid rocket instance");
+    let response = client.get("/").dispatch().await;
+    assert_eq!(response.status(), Status::BadRequest);
+    assert_eq!(response.content_type(), Some(ContentType::JSON));
+
+    assert_eq!(
+        response.into_string().await,
+        Some(
+            r#"{"errors":[{"message":"must not be empty","path":"name"},{"message":"already taken","path":"email"}],"message":"invalid form"}"#
+                .into()
+        )
+    );
+}
+
+#[derive(Debug, Error, SimpleResponder)]
+#[response(code = 400)]
+enum BatchError {
+    #[error("invalid batch")]
+    Invalid(#[response(errors)] Vec<FormError>),
+}
+
+#[get("/")]
+fn case9_route() -> BatchError {
+    BatchError::Invalid(vec![FormError::Taken { field: "email" }])
+}
+
+#[tokio::test]
+async fn case9() {
+    let rocket = rocket::ignite().mount("/", routes![case9_route]);
+    let client = Client::untracked(rocket)
+        .await
+        .expect("valid rocket instance");
+    let response = client.get("/").dispatch().await;
+    assert_eq!(response.status(), Status::BadRequest);
+    assert_eq!(
+        response.into_string().await,
+        Some("invalid batch\nemail: already taken".into())
+    );
+}
+
+#[tokio::test]
+async fn case10() {
+    let client = Client::untracked(rocket::ignite())
+        .await
+        .expect("valid rocket instance");
+    let request = client.get("/");
+    let mut info = ErrorInfo::new("Error", Status::NotFound, "not found");
+    info.error_code = Some("NOT_FOUND");
+    assert_eq!(
+        Json::format(&info, request.inner()),
+        (
+            ContentType::JSON,
+            r#"{"error_code":"NOT_FOUND","message":"not found"}"#.into()
+        )
+    );
+}
+
+#[derive(Debug, Error, SimpleResponder)]
+#[error("upstream failed")]
+#[response(code = 502, formatter = rocket_simple_responder::Json)]
+struct UpstreamError {
+    #[response(expose = "upstream_status")]
+    status: u16,
+    #[response(expose = "upstream_message")]
+    msg: String,
+    #[response(expose = "request_id")]
+    request: u64,
+}
+
+#[get("/")]
+fn case11_route() -> UpstreamError {
+    UpstreamError {
+        status: 503,
+        msg: "unavailable".into(),
+        request: 9,
+    }
+}
+
+#[tokio::test]
+async fn case11() {
+    // fields named like the locals of the generated code don't shadow them
+    let rocket = rocket::ignite().mount("/", routes![case11_route]);
+    let client = Client::untracked(rocket)
+        .await
+        .expect("valid rocket instance");
+    let response = client.get("/").dispatch().await;
+    assert_eq!(response.status(), Status::BadGateway);
+
+    let body: serde_json::Value =
+        serde_json::from_str(&response.into_string().await.unwrap()).unwrap();
+    assert_eq!(
+        body,
+        serde_json::json!({
+            "message": "upstream failed",
+            "upstream_status": 503,
+            "upstream_message": "unavailable",
+            "request_id": 9,
+        })
+    );
+}
@@ -1,10 +1,10 @@
 use rocket::{
-    get,
+    catch, catchers, get,
     http::{ContentType, Status},
     local::asynchronous::Client,
     routes,
 };
-use rocket_simple_responder::SimpleResponder;
+use rocket_simple_responder::{ErrorOverrides, SimpleResponder};
 use thiserror::Error;
 
 #[derive(Debug, Error, SimpleResponder)]
@@ -112,3 +112,301 @@ async fn case4() {
         Some(AuthError::Forbidden.to_string())
     );
 }
+
+#[tokio::test]
+async fn case5() {
+    let figment = rocket::Config::figment()
+        .merge(("error_overrides.Error::Other.code", 503))
+        .merge(("error_overrides.Error::Other.message", "try again later"));
+    let rocket = rocket::custom(figment)
+        .attach(ErrorOverrides::fairing())
+        .mount("/", routes![case3_route]);
+    let client = Client::untracked(rocket)
+        .await
+        .expect("valid rocket instance");
+    let response = client.get("/").dispatch().await;
+    assert_eq!(response.status(), Status::ServiceUnavailable);
+    assert_eq!(response.content_type(), Some(ContentType::Plain));
+
+    assert_eq!(response.into_string().await, Some("try again later".into()));
+}
+
+#[derive(Debug, Error, SimpleResponder)]
+#[response(code = 401, challenge(scheme = "Bearer", realm = "api"))]
+enum TokenError {
+    #[error("missing token")]
+    Missing,
+    #[error("invalid token")]
+    #[response(challenge(scheme = "Bearer", realm = "api", error = "invalid_token"))]
+    Invalid {
+        #[response(challenge)]
+        error_description: String,
+    },
+}
+
+#[get("/")]
+fn case6_route() -> TokenError {
+    TokenError::Missing
+}
+
+#[tokio::test]
+async fn case6() {
+    let rocket = rocket::ignite().mount("/", routes![case6_route]);
+    let client = Client::untracked(rocket)
+        .await
+        .expect("valid rocket instance");
+    let response = client.get("/").dispatch().await;
+    assert_eq!(response.status(), Status::Unauthorized);
+    assert_eq!(
+        response.headers().get_one("WWW-Authenticate"),
+        Some(r#"Bearer realm="api""#)
+    );
+}
+
+#[get("/")]
+fn case7_route() -> TokenError {
+    TokenError::Invalid {
+        error_description: r#"the "exp" claim has passed"#.into(),
+    }
+}
+
+#[tokio::test]
+async fn case7() {
+    let rocket = rocket::ignite().mount("/", routes![case7_route]);
+    let client = Client::untracked(rocket)
+        .await
+        .expect("valid rocket instance");
+    let response = client.get("/").dispatch().await;
+    assert_eq!(response.status(), Status::Unauthorized);
+    assert_eq!(
+        response.headers().get_one("WWW-Authenticate"),
+        Some(
+            r#"Bearer realm="api", error="invalid_token", error_description="the \"exp\" claim has passed""#
+        )
+    );
+}
+
+#[derive(Debug, Error, SimpleResponder)]
+#[response(code = 500, code_with = query_status)]
+enum QueryError {
+    #[error("query failed")]
+    Query(Option<u32>),
+    #[error("bad query")]
+    #[response(code = 400)]
+    BadQuery,
+}
+
+fn query_status(error: &QueryError) -> Status {
+    match error {
+        QueryError::Query(None) => Status::NotFound,
+        _ => Status::InternalServerError,
+    }
+}
+
+#[get("/")]
+fn case8_route() -> QueryError {
+    QueryError::Query(None)
+}
+
+#[tokio::test]
+async fn case8() {
+    let rocket = rocket::ignite().mount("/", routes![case8_route]);
+    let client = Client::untracked(rocket)
+        .await
+        .expect("valid rocket instance");
+    let response = client.get("/").dispatch().await;
+    assert_eq!(response.status(), Status::NotFound);
+}
+
+#[get("/")]
+fn case9_route() -> QueryError {
+    QueryError::Query(Some(1))
+}
+
+#[tokio::test]
+async fn case9() {
+    let rocket = rocket::ignite().mount("/", routes![case9_route]);
+    let client = Client::untracked(rocket)
+        .await
+        .expect("valid rocket instance");
+    let response = client.get("/").dispatch().await;
+    assert_eq!(response.status(), Status::InternalServerError);
+}
+
+#[get("/")]
+fn case10_route() -> QueryError {
+    QueryError::BadQuery
+}
+
+#[tokio::test]
+async fn case10() {
+    let rocket = rocket::ignite().mount("/", routes![case10_route]);
+    let client = Client::untracked(rocket)
+        .await
+        .expect("valid rocket instance");
+    let response = client.get("/").dispatch().await;
+    assert_eq!(response.status(), Status::BadRequest);
+}
+
+#[derive(Debug, Error, SimpleResponder)]
+enum CacheError {
+    #[error("not modified")]
+    #[response(code = 304)]
+    NotModified,
+}
+
+#[get("/")]
+fn case11_route() -> CacheError {
+    CacheError::NotModified
+}
+
+#[tokio::test]
+async fn case11() {
+    let rocket = rocket::ignite().mount("/", routes![case11_route]);
+    let client = Client::untracked(rocket)
+        .await
+        .expect("valid rocket instance");
+    let response = client.get("/").dispatch().await;
+    assert_eq!(response.status(), Status::NotModified);
+    assert_eq!(response.content_type(), None);
+    assert_eq!(response.into_string().await, None);
+}
+
+#[get("/")]
+fn case12_route() -> Error {
+    Error::NotFound
+}
+
+#[tokio::test]
+async fn case12() {
+    let rocket = rocket::ignite().mount("/", routes![case12_route]);
+    let client = Client::untracked(rocket)
+        .await
+        .expect("valid rocket instance");
+    let response = client.head("/").dispatch().await;
+    assert_eq!(response.status(), Status::NotFound);
+    assert_eq!(response.content_type(), Some(ContentType::Plain));
+    assert_eq!(
+        response.headers().get_one("Content-Length"),
+        Some(Error::NotFound.to_string().len().to_string().as_str())
+    );
+    assert_eq!(response.into_string().await, None);
+}
+
+#[derive(Debug, Error, SimpleResponder)]
+#[response(code = 400)]
+enum PageError {
+    #[error("page not found")]
+    #[response(forward = 404)]
+    NotFound,
+}
+
+#[catch(404)]
+fn not_found_page() -> &'static str {
+    "custom 404 page"
+}
+
+#[get("/")]
+fn case13_route() -> PageError {
+    PageError::NotFound
+}
+
+#[tokio::test]
+async fn case13() {
+    let rocket = rocket::ignite()
+        .mount("/", routes![case13_route])
+        .register(catchers![not_found_page]);
+    let client = Client::untracked(rocket)
+        .await
+        .expect("valid rocket instance");
+    let response = client.get("/").dispatch().await;
+    assert_eq!(response.status(), Status::NotFound);
+    assert_eq!(response.into_string().await, Some("custom 404 page".into()));
+}
+
+#[derive(Debug, Error, SimpleResponder)]
+enum LookupError {
+    #[error("user {0} not found")]
+    #[response(code = 404, message = "user not found")]
+    UserNotFound(u64),
+    #[error("{{resource}} not found")]
+    #[response(code = 404)]
+    ResourceNotFound,
+}
+
+#[get("/user")]
+fn case14_user() -> LookupError {
+    LookupError::UserNotFound(42)
+}
+
+#[get("/resource")]
+fn case14_resource() -> LookupError {
+    LookupError::ResourceNotFound
+}
+
+#[tokio::test]
+async fn case14() {
+    let rocket = rocket::ignite().mount("/", routes![case14_user, case14_resource]);
+    let client = Client::untracked(rocket)
+        .await
+        .expect("valid rocket instance");
+    let response = client.get("/user").dispatch().await;
+    assert_eq!(response.status(), Status::NotFound);
+    assert_eq!(response.into_string().await, Some("user not found".into()));
+
+    let response = client.get("/resource").dispatch().await;
+    assert_eq!(response.status(), Status::NotFound);
+    assert_eq!(
+        response.into_string().await,
+        Some("{resource} not found".into())
+    );
+}
+
+#[tokio::test]
+async fn case15() {
+    // without the fairing, the overrides are read from the configuration on each response,
+    // and those that can't be used are ignored
+    let figment = rocket::Config::figment()
+        .merge(("error_overrides.Error::Other.code", 503))
+        .merge(("error_overrides.Error::NotFound.code", 999))
+        .merge(("error_overrides.Error::NotFound.message", "gone"));
+    let rocket = rocket::custom(figment)
+        .mount("/other", routes![case3_route])
+        .mount("/not-found", routes![case2_route]);
+    let client = Client::untracked(rocket)
+        .await
+        .expect("valid rocket instance");
+    let response = client.get("/other").dispatch().await;
+    assert_eq!(response.status(), Status::ServiceUnavailable);
+    assert_eq!(response.into_string().await, Some(Error::Other.to_string()));
+
+    let response = client.get("/not-found").dispatch().await;
+    assert_eq!(response.status(), Status::NotFound);
+    assert_eq!(response.into_string().await, Some("gone".into()));
+}
+
+#[tokio::test]
+async fn case16() {
+    // the challenge follows the status that is actually sent
+    let figment = rocket::Config::figment()
+        .merge(("error_overrides.TokenError::Missing.code", 503))
+        .merge(("error_overrides.TokenError::Invalid.code", 407));
+    let rocket = rocket::custom(figment)
+        .mount("/missing", routes![case6_route])
+        .mount("/invalid", routes![case7_route]);
+    let client = Client::untracked(rocket)
+        .await
+        .expect("valid rocket instance");
+    let response = client.get("/missing").dispatch().await;
+    assert_eq!(response.status(), Status::ServiceUnavailable);
+    assert_eq!(response.headers().get_one("WWW-Authenticate"), None);
+
+    let response = client.get("/invalid").dispatch().await;
+    assert_eq!(response.status(), Status::ProxyAuthenticationRequired);
+    assert_eq!(response.headers().get_one("WWW-Authenticate"), None);
+    assert!(response
+        .headers()
+        .get_one("Proxy-Authenticate")
+        .unwrap()
+        .starts_with(r#"Bearer realm="api", error="invalid_token""#));
+}
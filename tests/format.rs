@@ -0,0 +1,168 @@
+use rocket::{
+    get,
+    http::{ContentType, Status},
+    local::asynchronous::Client,
+    routes, Request,
+};
+use rocket_simple_responder::{ErrorFormatter, ErrorInfo, SimpleResponder};
+use thiserror::Error;
+
+struct Envelope;
+
+impl ErrorFormatter for Envelope {
+    fn format(info: &ErrorInfo<'_>, request: &Request<'_>) -> (ContentType, String) {
+        let body = format!(
+            "{} {} {} {} {}",
+            request.uri(),
+            info.status.code,
+            info.type_name,
+            info.variant.unwrap_or("-"),
+            info.message
+        );
+        (ContentType::HTML, body)
+    }
+}
+
+#[derive(Debug, Error, SimpleResponder)]
+#[response(formatter = Envelope)]
+enum Error {
+    #[error("not found")]
+    #[response(code = 404)]
+    NotFound,
+    #[error("bad request")]
+    #[response(code = 400)]
+    BadRequest,
+}
+
+#[derive(Debug, Error, SimpleResponder)]
+#[error("internal error")]
+#[response(code = 500, formatter = Envelope)]
+struct InternalError;
+
+#[get("/not-found")]
+fn case1_route() -> Error {
+    Error::NotFound
+}
+
+#[tokio::test]
+async fn case1() {
+    let rocket = rocket::ignite().mount("/", routes![case1_route]);
+    let client = Client::untracked(rocket)
+        .await
+        .expect("valid rocket instance");
+    let response = client.get("/not-found").dispatch().await;
+    assert_eq!(response.status(), Status::NotFound);
+    assert_eq!(response.content_type(), Some(ContentType::HTML));
+
+    assert_eq!(
+        response.into_string().await,
+        Some("/not-found 404 Error NotFound not found".into())
+    );
+}
+
+#[get("/")]
+fn case2_route() -> InternalError {
+    InternalError
+}
+
+#[tokio::test]
+async fn case2() {
+    let rocket = rocket::ignite().mount("/", routes![case2_route]);
+    let client = Client::untracked(rocket)
+        .await
+        .expect("valid rocket instance");
+    let response = client.get("/").dispatch().await;
+    assert_eq!(response.status(), Status::InternalServerError);
+    assert_eq!(response.content_type(), Some(ContentType::HTML));
+
+    assert_eq!(
+        response.into_string().await,
+        Some("/ 500 InternalError - internal error".into())
+    );
+}
+
+#[derive(Debug, Error, SimpleResponder)]
+#[response(code = 400, content_type = "text/markdown; charset=utf-8")]
+enum BotError {
+    #[error("**unknown command**")]
+    UnknownCommand,
+    #[error("not found")]
+    #[response(code = 404, formatter = Envelope, content_type = "application/vnd.acme.error")]
+    NotFound,
+}
+
+#[get("/")]
+fn case3_route() -> BotError {
+    BotError::UnknownCommand
+}
+
+#[tokio::test]
+async fn case3() {
+    let rocket = rocket::ignite().mount("/", routes![case3_route]);
+    let client = Client::untracked(rocket)
+        .await
+        .expect("valid rocket instance");
+    let response = client.get("/").dispatch().await;
+    assert_eq!(response.status(), Status::BadRequest);
+    assert_eq!(
+        response.content_type(),
+        Some(ContentType::new("text", "markdown").with_params(("charset", "utf-8")))
+    );
+
+    assert_eq!(
+        response.into_string().await,
+        Some("**unknown command**".into())
+    );
+}
+
+#[get("/")]
+fn case4_route() -> BotError {
+    BotError::NotFound
+}
+
+#[tokio::test]
+async fn case4() {
+    let rocket = rocket::ignite().mount("/", routes![case4_route]);
+    let client = Client::untracked(rocket)
+        .await
+        .expect("valid rocket instance");
+    let response = client.get("/").dispatch().await;
+    assert_eq!(response.status(), Status::NotFound);
+    assert_eq!(
+        response.content_type(),
+        Some(ContentType::new("application", "vnd.acme.error"))
+    );
+
+    assert_eq!(
+        response.into_string().await,
+        Some("/ 404 BotError NotFound not found".into())
+    );
+}
+
+#[derive(Debug, Error, SimpleResponder)]
+#[error("**gone**")]
+#[response(
+    code = 410,
+    content_type = "text/markdown; charset=\"utf-8\"; variant=\"Common\\Mark\""
+)]
+struct Gone;
+
+#[get("/")]
+fn case5_route() -> Gone {
+    Gone
+}
+
+#[tokio::test]
+async fn case5() {
+    let rocket = rocket::ignite().mount("/", routes![case5_route]);
+    let client = Client::untracked(rocket)
+        .await
+        .expect("valid rocket instance");
+    let response = client.get("/").dispatch().await;
+    assert_eq!(response.status(), Status::Gone);
+    // quoted values are sent unquoted, as `ContentType` doesn't compare the parameters
+    assert_eq!(
+        response.headers().get_one("Content-Type"),
+        Some("text/markdown; charset=utf-8; variant=CommonMark")
+    );
+}
@@ -0,0 +1,67 @@
+use rocket_simple_responder::SimpleResponder;
+use thiserror::Error;
+use tonic::{Code, Status};
+
+#[derive(Debug, Error, SimpleResponder)]
+#[response(code = 500)]
+enum Error {
+    #[error("not found")]
+    #[response(code = 404)]
+    NotFound,
+    #[error("out of range")]
+    #[response(code = 400, grpc = "OutOfRange")]
+    OutOfRange(u32),
+    #[error("auth error")]
+    Auth(#[response(delegate)] AuthError),
+    #[error("other")]
+    Other,
+    #[error("moved")]
+    #[response(code = 303)]
+    Moved {
+        #[response(location)]
+        location: String,
+    },
+}
+
+#[derive(Debug, Error, SimpleResponder)]
+enum AuthError {
+    #[error("unauthorized")]
+    #[response(code = 401)]
+    Unauthorized,
+}
+
+#[derive(Debug, Error, SimpleResponder)]
+#[error("unavailable")]
+#[response(code = 500, grpc = "Unavailable")]
+struct Unavailable;
+
+#[test]
+fn case1() {
+    let status = Status::from(Error::NotFound);
+    assert_eq!(status.code(), Code::NotFound);
+    assert_eq!(status.message(), "not found");
+
+    assert_eq!(Status::from(Error::OutOfRange(3)).code(), Code::OutOfRange);
+    assert_eq!(Status::from(Error::Other).code(), Code::Internal);
+}
+
+#[test]
+fn case2() {
+    let status = Status::from(Error::Auth(AuthError::Unauthorized));
+    assert_eq!(status.code(), Code::Unauthenticated);
+    assert_eq!(status.message(), "unauthorized");
+}
+
+#[test]
+fn case3() {
+    assert_eq!(Status::from(Unavailable).code(), Code::Unavailable);
+}
+
+#[test]
+fn case4() {
+    let status = Status::from(Error::Moved {
+        location: "/elsewhere".into(),
+    });
+    assert_eq!(status.code(), Code::Unknown);
+    assert_eq!(status.message(), "moved");
+}
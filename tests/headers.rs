@@ -0,0 +1,201 @@
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use rocket::{get, http::Status, local::asynchronous::Client, routes};
+use rocket_simple_responder::{RateLimitValue, RetryAfter, SimpleResponder};
+use thiserror::Error;
+
+#[derive(Debug, Error, SimpleResponder)]
+enum Error {
+    #[error("too many requests")]
+    #[response(code = 429)]
+    TooManyRequests {
+        #[response(retry_after, ratelimit_reset)]
+        wait: Duration,
+        #[response(ratelimit_limit)]
+        limit: u32,
+        #[response(ratelimit_remaining)]
+        remaining: u32,
+    },
+    #[error("down for maintenance")]
+    #[response(code = 503)]
+    Maintenance(#[response(retry_after)] SystemTime),
+    #[error("session expired")]
+    #[response(code = 303)]
+    SessionExpired {
+        #[response(location)]
+        login: String,
+    },
+}
+
+#[test]
+fn retry_after() {
+    assert_eq!(Duration::from_millis(1500).retry_after(), Some("2".into()));
+    assert_eq!(
+        (UNIX_EPOCH + Duration::from_secs(784111777)).retry_after(),
+        Some("Sun, 06 Nov 1994 08:49:37 GMT".into())
+    );
+    assert_eq!(None::<Duration>.retry_after(), None);
+    assert_eq!(Some(Duration::from_secs(3)).rate_limit_value(), Some(3));
+}
+
+#[get("/")]
+fn case1_route() -> Error {
+    Error::TooManyRequests {
+        wait: Duration::from_secs(30),
+        limit: 100,
+        remaining: 0,
+    }
+}
+
+#[tokio::test]
+async fn case1() {
+    let rocket = rocket::ignite().mount("/", routes![case1_route]);
+    let client = Client::untracked(rocket)
+        .await
+        .expect("valid rocket instance");
+    let response = client.get("/").dispatch().await;
+    assert_eq!(response.status(), Status::TooManyRequests);
+    assert_eq!(response.headers().get_one("Retry-After"), Some("30"));
+    assert_eq!(response.headers().get_one("RateLimit-Limit"), Some("100"));
+    assert_eq!(response.headers().get_one("RateLimit-Remaining"), Some("0"));
+    assert_eq!(response.headers().get_one("RateLimit-Reset"), Some("30"));
+}
+
+#[get("/")]
+fn case2_route() -> Error {
+    Error::Maintenance(UNIX_EPOCH + Duration::from_secs(784111777))
+}
+
+#[tokio::test]
+async fn case2() {
+    let rocket = rocket::ignite().mount("/", routes![case2_route]);
+    let client = Client::untracked(rocket)
+        .await
+        .expect("valid rocket instance");
+    let response = client.get("/").dispatch().await;
+    assert_eq!(response.status(), Status::ServiceUnavailable);
+    assert_eq!(
+        response.headers().get_one("Retry-After"),
+        Some("Sun, 06 Nov 1994 08:49:37 GMT")
+    );
+}
+
+#[get("/")]
+fn case3_route() -> Error {
+    Error::SessionExpired {
+        login: "/login?next=%2F".into(),
+    }
+}
+
+#[tokio::test]
+async fn case3() {
+    let rocket = rocket::ignite().mount("/", routes![case3_route]);
+    let client = Client::untracked(rocket)
+        .await
+        .expect("valid rocket instance");
+    let response = client.get("/").dispatch().await;
+    assert_eq!(response.status(), Status::SeeOther);
+    assert_eq!(
+        response.headers().get_one("Location"),
+        Some("/login?next=%2F")
+    );
+    assert_eq!(response.content_type(), None);
+    assert_eq!(response.into_string().await, None);
+}
+
+#[derive(Debug, Error, SimpleResponder)]
+#[response(code = 403, error_code = auto)]
+enum AuthError {
+    #[error("forbidden")]
+    Forbidden,
+    #[error("login required")]
+    #[response(code = 401, error_code = "AUTH_LOGIN_REQUIRED")]
+    LoginRequired,
+}
+
+#[get("/")]
+fn case4_route() -> AuthError {
+    AuthError::Forbidden
+}
+
+#[tokio::test]
+async fn case4() {
+    let rocket = rocket::ignite().mount("/", routes![case4_route]);
+    let client = Client::untracked(rocket)
+        .await
+        .expect("valid rocket instance");
+    let response = client.get("/").dispatch().await;
+    assert_eq!(response.status(), Status::Forbidden);
+    assert_eq!(
+        response.headers().get_one("X-Error-Code"),
+        Some("AUTH_ERROR_FORBIDDEN")
+    );
+}
+
+#[get("/")]
+fn case5_route() -> AuthError {
+    AuthError::LoginRequired
+}
+
+#[tokio::test]
+async fn case5() {
+    let rocket = rocket::ignite().mount("/", routes![case5_route]);
+    let client = Client::untracked(rocket)
+        .await
+        .expect("valid rocket instance");
+    let response = client.get("/").dispatch().await;
+    assert_eq!(response.status(), Status::Unauthorized);
+    assert_eq!(
+        response.headers().get_one("X-Error-Code"),
+        Some("AUTH_LOGIN_REQUIRED")
+    );
+}
+
+#[tokio::test]
+async fn case6() {
+    // redirecting to the login page doesn't make sense when responding with an error instead
+    let figment =
+        rocket::Config::figment().merge(("error_overrides.Error::SessionExpired.code", 500));
+    let rocket = rocket::custom(figment).mount("/", routes![case3_route]);
+    let client = Client::untracked(rocket)
+        .await
+        .expect("valid rocket instance");
+    let response = client.get("/").dispatch().await;
+    assert_eq!(response.status(), Status::InternalServerError);
+    assert_eq!(response.headers().get_one("Location"), None);
+}
+
+#[derive(Debug, Error, SimpleResponder)]
+#[error("moved")]
+#[response(code = 308)]
+struct Moved {
+    #[response(location)]
+    status: String,
+    #[response(retry_after)]
+    request: Duration,
+    #[response(ratelimit_remaining)]
+    headers: u32,
+}
+
+#[get("/")]
+fn case7_route() -> Moved {
+    Moved {
+        status: "/elsewhere".into(),
+        request: Duration::from_secs(5),
+        headers: 0,
+    }
+}
+
+#[tokio::test]
+async fn case7() {
+    // fields named like the locals of the generated code don't shadow them
+    let rocket = rocket::ignite().mount("/", routes![case7_route]);
+    let client = Client::untracked(rocket)
+        .await
+        .expect("valid rocket instance");
+    let response = client.get("/").dispatch().await;
+    assert_eq!(response.status(), Status::PermanentRedirect);
+    assert_eq!(response.headers().get_one("Location"), Some("/elsewhere"));
+    assert_eq!(response.headers().get_one("Retry-After"), Some("5"));
+    assert_eq!(response.headers().get_one("RateLimit-Remaining"), Some("0"));
+}
@@ -0,0 +1,310 @@
+use std::{cell::RefCell, net::SocketAddr, sync::Once};
+
+use log::{Level, LevelFilter, Log, Metadata, Record};
+use rocket::{get, local::asynchronous::Client, routes, Route};
+use rocket_simple_responder::SimpleResponder;
+use thiserror::Error;
+
+/// Captures the records logged by this crate. They are kept per thread, as each test dispatches
+/// its requests on its own thread.
+struct Capture;
+
+thread_local! {
+    static RECORDS: RefCell<Vec<(Level, String)>> = RefCell::new(Vec::new());
+}
+
+impl Log for Capture {
+    fn enabled(&self, _metadata: &Metadata<'_>) -> bool {
+        true
+    }
+
+    fn log(&self, record: &Record<'_>) {
+        let from_crate = record
+            .module_path()
+            .map_or(false, |path| path.starts_with("rocket_simple_responder"));
+        if from_crate {
+            RECORDS.with(|records| {
+                records
+                    .borrow_mut()
+                    .push((record.level(), record.args().to_string()))
+            });
+        }
+    }
+
+    fn flush(&self) {}
+}
+
+static INIT: Once = Once::new();
+
+/// A client for `routes`, with the records of the current thread captured from now on.
+///
+/// Rocket's own logging is turned off, so that it doesn't replace the logger nor change the
+/// maximum level.
+async fn client(routes: Vec<Route>) -> Client {
+    INIT.call_once(|| {
+        log::set_logger(&Capture).expect("no other logger");
+        log::set_max_level(LevelFilter::Trace);
+    });
+    RECORDS.with(|records| records.borrow_mut().clear());
+    let figment = rocket::Config::figment().merge(("log_level", "off"));
+    let rocket = rocket::custom(figment).mount("/", routes);
+    Client::untracked(rocket)
+        .await
+        .expect("valid rocket instance")
+}
+
+fn remote() -> SocketAddr {
+    "127.0.0.1:8000".parse().unwrap()
+}
+
+/// Takes the records captured on the current thread.
+fn records() -> Vec<(Level, String)> {
+    RECORDS.with(|records| records.borrow_mut().drain(..).collect())
+}
+
+#[derive(Debug, Error, SimpleResponder)]
+enum UserError {
+    #[error("user {0} not found")]
+    #[response(code = 404)]
+    NotFound(u64),
+}
+
+#[get("/users/<id>")]
+fn user(id: u64) -> UserError {
+    UserError::NotFound(id)
+}
+
+#[tokio::test]
+async fn case1() {
+    let client = client(routes![user]).await;
+    client.get("/users/42").remote(remote()).dispatch().await;
+    // the request and the message, then the `Debug` of the error rather than its message
+    assert_eq!(
+        records(),
+        vec![
+            (
+                Level::Warn,
+                "GET /users/42 (route: user, client: 127.0.0.1): user 42 not found".into()
+            ),
+            (Level::Warn, "NotFound(42)".into()),
+        ]
+    );
+}
+
+#[derive(Debug, Error, SimpleResponder)]
+#[response(code = 500)]
+enum Error {
+    #[error("moved")]
+    #[response(code = 303)]
+    Moved,
+    #[error("not found")]
+    #[response(code = 404)]
+    NotFound,
+    #[error("upstream failed")]
+    Upstream,
+    #[error("probably a typo")]
+    #[response(code = 404, log = "debug")]
+    Typo,
+    #[error("tampered")]
+    #[response(code = 400, log = "error")]
+    Tampered,
+    #[error("expected")]
+    #[response(code = 500, log = "off")]
+    Expected,
+}
+
+#[get("/<name>")]
+fn error(name: String) -> Error {
+    match name.as_str() {
+        "moved" => Error::Moved,
+        "not-found" => Error::NotFound,
+        "typo" => Error::Typo,
+        "tampered" => Error::Tampered,
+        "expected" => Error::Expected,
+        _ => Error::Upstream,
+    }
+}
+
+/// The levels of the records logged for a request to `uri`.
+async fn levels(client: &Client, uri: &str) -> Vec<Level> {
+    client.get(uri).dispatch().await;
+    records().into_iter().map(|(level, _)| level).collect()
+}
+
+#[tokio::test]
+async fn case2() {
+    // by status, for the levels that aren't specified
+    let client = client(routes![error]).await;
+    assert_eq!(levels(&client, "/moved").await, [Level::Info, Level::Info]);
+    assert_eq!(
+        levels(&client, "/not-found").await,
+        [Level::Warn, Level::Warn]
+    );
+    assert_eq!(
+        levels(&client, "/upstream").await,
+        [Level::Error, Level::Error]
+    );
+}
+
+#[tokio::test]
+async fn case3() {
+    let client = client(routes![error]).await;
+    assert_eq!(levels(&client, "/typo").await, [Level::Debug, Level::Debug]);
+    assert_eq!(
+        levels(&client, "/tampered").await,
+        [Level::Error, Level::Error]
+    );
+    assert!(levels(&client, "/expected").await.is_empty());
+}
+
+#[derive(Debug, Error, SimpleResponder)]
+enum AuthError {
+    #[error("unauthorized")]
+    #[response(code = 401)]
+    Unauthorized,
+}
+
+#[derive(Debug, Error, SimpleResponder)]
+enum ApiError {
+    #[error("auth error")]
+    Auth(#[response(delegate)] AuthError),
+    #[error("audited auth error")]
+    #[response(log = "info")]
+    Audited(#[response(delegate)] AuthError),
+    #[error("quiet auth error")]
+    #[response(log = "off")]
+    Quiet(#[response(delegate)] AuthError),
+}
+
+#[derive(Debug, Error, SimpleResponder)]
+#[error("session error")]
+#[response(log = "error")]
+struct SessionError(#[response(delegate)] ApiError);
+
+#[get("/auth")]
+fn auth() -> ApiError {
+    ApiError::Auth(AuthError::Unauthorized)
+}
+
+#[get("/audited")]
+fn audited() -> ApiError {
+    ApiError::Audited(AuthError::Unauthorized)
+}
+
+#[get("/quiet")]
+fn quiet() -> ApiError {
+    ApiError::Quiet(AuthError::Unauthorized)
+}
+
+#[get("/session")]
+fn session() -> SessionError {
+    SessionError(ApiError::Audited(AuthError::Unauthorized))
+}
+
+#[tokio::test]
+async fn case4() {
+    let client = client(routes![auth, audited, quiet, session]).await;
+    // without a level, only the delegate logs
+    client.get("/auth").remote(remote()).dispatch().await;
+    assert_eq!(
+        records(),
+        vec![
+            (
+                Level::Warn,
+                "GET /auth (route: auth, client: 127.0.0.1): unauthorized".into()
+            ),
+            (Level::Warn, "Unauthorized".into()),
+        ]
+    );
+
+    // with a level, only the delegating variant logs
+    client.get("/audited").remote(remote()).dispatch().await;
+    assert_eq!(
+        records(),
+        vec![
+            (
+                Level::Info,
+                "GET /audited (route: audited, client: 127.0.0.1): audited auth error".into()
+            ),
+            (Level::Info, "Audited(Unauthorized)".into()),
+        ]
+    );
+
+    client.get("/quiet").dispatch().await;
+    assert!(records().is_empty());
+
+    // the outermost delegating type logs, once
+    client.get("/session").remote(remote()).dispatch().await;
+    assert_eq!(
+        records(),
+        vec![
+            (
+                Level::Error,
+                "GET /session (route: session, client: 127.0.0.1): session error".into()
+            ),
+            (Level::Error, "SessionError(Audited(Unauthorized))".into()),
+        ]
+    );
+
+    // the delegates log again once the delegating responses are built
+    client.get("/auth").dispatch().await;
+    assert_eq!(records().len(), 2);
+}
+
+#[derive(Debug, Error, SimpleResponder)]
+enum TokenError {
+    #[error("invalid token {token} for {user}")]
+    #[response(code = 401)]
+    Invalid {
+        #[response(sensitive)]
+        token: String,
+        user: String,
+    },
+    #[error("expired token {0}")]
+    #[response(code = 401, message = "expired token")]
+    Expired(#[response(sensitive)] String),
+}
+
+#[get("/invalid")]
+fn invalid() -> TokenError {
+    TokenError::Invalid {
+        token: "s3cr3t".into(),
+        user: "alice".into(),
+    }
+}
+
+#[get("/expired")]
+fn expired() -> TokenError {
+    TokenError::Expired("s3cr3t".into())
+}
+
+#[tokio::test]
+async fn case5() {
+    let client = client(routes![invalid, expired]).await;
+    client.get("/invalid").remote(remote()).dispatch().await;
+    assert_eq!(
+        records(),
+        vec![
+            (
+                Level::Warn,
+                "GET /invalid (route: invalid, client: 127.0.0.1): ***".into()
+            ),
+            (
+                Level::Warn,
+                r#"Invalid { token: ***, user: "alice" }"#.into()
+            ),
+        ]
+    );
+
+    client.get("/expired").remote(remote()).dispatch().await;
+    assert_eq!(
+        records(),
+        vec![
+            (
+                Level::Warn,
+                "GET /expired (route: expired, client: 127.0.0.1): expired token".into()
+            ),
+            (Level::Warn, "Expired(***)".into()),
+        ]
+    );
+}
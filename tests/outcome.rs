@@ -0,0 +1,112 @@
+use rocket::{
+    catch, catchers, get,
+    http::{ContentType, Header, Status},
+    local::asynchronous::Client,
+    outcome::Outcome,
+    request::{self, FromRequest},
+    routes, Request,
+};
+use rocket_simple_responder::{cache_error, CachedError, SimpleResponder};
+use thiserror::Error;
+
+#[derive(Debug, Clone, Error, SimpleResponder)]
+enum AuthError {
+    #[error("missing api key")]
+    #[response(code = 401)]
+    Missing,
+    #[error("invalid api key")]
+    #[response(code = 403)]
+    Invalid,
+}
+
+struct ApiKey;
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for ApiKey {
+    type Error = AuthError;
+
+    async fn from_request(request: &'r Request<'_>) -> request::Outcome<Self, Self::Error> {
+        let error = match request.headers().get_one("x-api-key") {
+            Some("secret") => return Outcome::Success(ApiKey),
+            Some(_) => AuthError::Invalid,
+            None => AuthError::Missing,
+        };
+        cache_error(request, error.clone());
+        error.into()
+    }
+}
+
+#[catch(401)]
+fn unauthorized(request: &Request<'_>) -> CachedError<AuthError> {
+    CachedError::new(request, Status::Unauthorized)
+}
+
+#[catch(403)]
+fn forbidden(request: &Request<'_>) -> CachedError<AuthError> {
+    CachedError::new(request, Status::Forbidden)
+}
+
+#[test]
+fn case1() {
+    match AuthError::Invalid.into_outcome::<ApiKey>() {
+        Outcome::Failure((status, AuthError::Invalid)) => assert_eq!(status, Status::Forbidden),
+        _ => panic!("expected a failure"),
+    }
+}
+
+#[get("/")]
+fn case2_route(_key: ApiKey) -> &'static str {
+    "ok"
+}
+
+#[tokio::test]
+async fn case2() {
+    let rocket = rocket::ignite()
+        .mount("/", routes![case2_route])
+        .register(catchers![unauthorized, forbidden]);
+    let client = Client::untracked(rocket)
+        .await
+        .expect("valid rocket instance");
+
+    let response = client.get("/").dispatch().await;
+    assert_eq!(response.status(), Status::Unauthorized);
+    assert_eq!(response.content_type(), Some(ContentType::Plain));
+    assert_eq!(response.into_string().await, Some("missing api key".into()));
+
+    let response = client
+        .get("/")
+        .header(Header::new("x-api-key", "wrong"))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Forbidden);
+    assert_eq!(response.into_string().await, Some("invalid api key".into()));
+}
+
+struct Session;
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for Session {
+    type Error = AuthError;
+
+    async fn from_request(_request: &'r Request<'_>) -> request::Outcome<Self, Self::Error> {
+        AuthError::Missing.into_outcome()
+    }
+}
+
+#[get("/")]
+fn case3_route(_session: Session) -> &'static str {
+    "ok"
+}
+
+#[tokio::test]
+async fn case3() {
+    let rocket = rocket::ignite()
+        .mount("/", routes![case3_route])
+        .register(catchers![unauthorized]);
+    let client = Client::untracked(rocket)
+        .await
+        .expect("valid rocket instance");
+    let response = client.get("/").dispatch().await;
+    assert_eq!(response.status(), Status::Unauthorized);
+    assert_eq!(response.content_type(), None);
+}
@@ -0,0 +1,44 @@
+use rocket::{get, http::Status, local::asynchronous::Client, routes};
+use rocket_simple_responder::{Redacted, SimpleResponder};
+use thiserror::Error;
+
+#[test]
+fn case1() {
+    assert_eq!(format!("{:?}", Redacted("s3cr3t")), "***");
+    assert_eq!(format!("{}", Redacted("s3cr3t")), "***");
+}
+
+#[derive(Debug, Error, SimpleResponder)]
+enum TokenError {
+    #[error("invalid token {0}")]
+    #[response(code = 401)]
+    Invalid(#[response(sensitive)] String),
+    #[error("expired token {0}")]
+    #[response(code = 401, message = "expired token")]
+    Expired(#[response(sensitive)] String),
+}
+
+#[get("/invalid")]
+fn invalid() -> TokenError {
+    TokenError::Invalid("s3cr3t".into())
+}
+
+#[get("/expired")]
+fn expired() -> TokenError {
+    TokenError::Expired("s3cr3t".into())
+}
+
+#[tokio::test]
+async fn case2() {
+    let rocket = rocket::ignite().mount("/", routes![invalid, expired]);
+    let client = Client::untracked(rocket)
+        .await
+        .expect("valid rocket instance");
+    // the message rendering the token is replaced as a whole
+    let response = client.get("/invalid").dispatch().await;
+    assert_eq!(response.status(), Status::Unauthorized);
+    assert_eq!(response.into_string().await, Some("***".into()));
+
+    let response = client.get("/expired").dispatch().await;
+    assert_eq!(response.into_string().await, Some("expired token".into()));
+}
@@ -0,0 +1,63 @@
+use rocket_simple_responder::{registry, SimpleResponder};
+use thiserror::Error;
+
+#[derive(Debug, Error, SimpleResponder)]
+#[response(code = 500)]
+enum Error {
+    #[error("not found")]
+    #[response(code = 404, error_code = "NOT_FOUND")]
+    NotFound,
+    #[error("auth error")]
+    Auth(#[response(delegate)] AuthError),
+}
+
+#[derive(Debug, Error, SimpleResponder)]
+#[response(error_code = auto)]
+enum AuthError {
+    #[error("forbidden")]
+    #[response(code = 403)]
+    Forbidden,
+}
+
+#[derive(Debug, Error, SimpleResponder)]
+#[error("missing")]
+#[response(code = 404, error_code = "NOT_FOUND")]
+struct Missing;
+
+#[test]
+fn case1() {
+    let error = registry::iter()
+        .find(|e| e.type_name == "Error")
+        .expect("registered type");
+    assert_eq!(error.module_path, "registry");
+    assert_eq!(error.variants.len(), 2);
+    assert_eq!(error.variants[0].name, Some("NotFound"));
+    assert_eq!(error.variants[0].code, Some(404));
+    assert_eq!(error.variants[0].error_code, Some("NOT_FOUND"));
+    assert_eq!(error.variants[1].name, Some("Auth"));
+    assert_eq!(error.variants[1].code, None);
+    assert_eq!(error.variants[1].delegate, Some("AuthError"));
+
+    let auth_error = registry::iter()
+        .find(|e| e.type_name == "AuthError")
+        .expect("registered type");
+    assert_eq!(
+        auth_error.variants[0].error_code,
+        Some("AUTH_ERROR_FORBIDDEN")
+    );
+}
+
+#[test]
+fn case2() {
+    // the duplicates are only reported by the registry, both types still respond as usual
+    assert_eq!(Error::NotFound.to_string(), "not found");
+    assert_eq!(Error::Auth(AuthError::Forbidden).to_string(), "auth error");
+
+    let duplicates = registry::duplicate_error_codes();
+    assert_eq!(duplicates.len(), 1);
+    assert_eq!(duplicates[0].error_code, "NOT_FOUND");
+    assert_eq!(
+        duplicates[0].variants,
+        vec!["registry::Error::NotFound", "registry::Missing"]
+    );
+}
@@ -0,0 +1,130 @@
+use std::fmt;
+
+use rocket::{
+    get,
+    http::{Status, StatusClass},
+    local::asynchronous::Client,
+    routes,
+};
+use rocket_simple_responder::{MemoryReporter, Report, Reporter, SimpleResponder};
+use thiserror::Error;
+
+#[derive(Debug, Error, SimpleResponder)]
+#[response(code = 500)]
+enum Error {
+    #[error("not found")]
+    #[response(code = 404)]
+    NotFound,
+    #[error("upstream failed")]
+    Upstream,
+    #[error("page not found")]
+    #[response(forward = 404)]
+    Page,
+}
+
+#[get("/upstream")]
+fn upstream() -> Error {
+    Error::Upstream
+}
+
+#[get("/not-found")]
+fn not_found() -> Error {
+    Error::NotFound
+}
+
+#[get("/page")]
+fn page() -> Error {
+    Error::Page
+}
+
+#[tokio::test]
+async fn case1() {
+    let reporter = MemoryReporter::new();
+    let rocket = rocket::ignite()
+        .mount("/", routes![upstream, not_found])
+        .manage(Reporter::new(reporter.clone()));
+    let client = Client::untracked(rocket)
+        .await
+        .expect("valid rocket instance");
+    client.get("/not-found").dispatch().await;
+    client.get("/upstream").dispatch().await;
+    assert_eq!(
+        reporter.reports(),
+        vec![Report {
+            status: Status::InternalServerError,
+            message: "upstream failed".into(),
+            uri: "/upstream".into(),
+        }]
+    );
+}
+
+#[tokio::test]
+async fn case2() {
+    let reporter = MemoryReporter::new();
+    let rocket = rocket::ignite()
+        .mount("/", routes![upstream, not_found])
+        .manage(Reporter::new(reporter.clone()).classes(&[StatusClass::ClientError]));
+    let client = Client::untracked(rocket)
+        .await
+        .expect("valid rocket instance");
+    client.get("/not-found").dispatch().await;
+    client.get("/upstream").dispatch().await;
+    assert_eq!(
+        reporter.reports(),
+        vec![Report {
+            status: Status::NotFound,
+            message: "not found".into(),
+            uri: "/not-found".into(),
+        }]
+    );
+}
+
+#[tokio::test]
+async fn case3() {
+    let reporter = MemoryReporter::new();
+    let rocket = rocket::ignite()
+        .mount("/", routes![page])
+        .manage(Reporter::new(reporter.clone()).classes(&[StatusClass::ClientError]));
+    let client = Client::untracked(rocket)
+        .await
+        .expect("valid rocket instance");
+    let response = client.get("/page").dispatch().await;
+    assert_eq!(response.status(), Status::NotFound);
+    assert_eq!(
+        reporter.reports(),
+        vec![Report {
+            status: Status::NotFound,
+            message: "page not found".into(),
+            uri: "/page".into(),
+        }]
+    );
+}
+
+#[derive(Debug, SimpleResponder)]
+#[response(code = 500)]
+struct Unreported;
+
+impl fmt::Display for Unreported {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("unreported")
+    }
+}
+
+#[get("/")]
+fn case4_route() -> Unreported {
+    Unreported
+}
+
+#[tokio::test]
+async fn case4() {
+    let reporter = MemoryReporter::new();
+    let rocket = rocket::ignite()
+        .mount("/", routes![case4_route])
+        .manage(Reporter::new(reporter.clone()));
+    let client = Client::untracked(rocket)
+        .await
+        .expect("valid rocket instance");
+    let response = client.get("/").dispatch().await;
+    assert_eq!(response.status(), Status::InternalServerError);
+    assert_eq!(reporter.reports(), vec![]);
+}
@@ -0,0 +1,51 @@
+use rocket::http::{ContentType, Status};
+use rocket_simple_responder::{
+    assert_responds,
+    testing::{respond, respond_examples},
+    SimpleResponder,
+};
+use thiserror::Error;
+
+#[derive(Debug, Error, SimpleResponder)]
+#[response(code = 500)]
+enum Error {
+    #[error("not found")]
+    #[response(code = 404, example = Error::NotFound)]
+    NotFound,
+    #[error("auth error")]
+    #[response(example = Error::Auth(AuthError::Forbidden))]
+    Auth(#[response(delegate)] AuthError),
+    #[error("other")]
+    Other,
+}
+
+#[derive(Debug, Error, SimpleResponder)]
+enum AuthError {
+    #[error("forbidden")]
+    #[response(code = 403)]
+    Forbidden,
+}
+
+#[tokio::test]
+async fn case1() {
+    let response = respond(Error::NotFound).await;
+    assert_eq!(response.status, Status::NotFound);
+    assert_eq!(response.content_type, Some(ContentType::Plain));
+    assert_eq!(response.body, "not found");
+}
+
+#[tokio::test]
+async fn case2() {
+    assert_responds!(Error::Other, 500, "other");
+    let response = assert_responds!(Error::Auth(AuthError::Forbidden), 403);
+    assert_eq!(response.body, "forbidden");
+}
+
+#[tokio::test]
+async fn case3() {
+    let examples = respond_examples::<Error>().await;
+    let keys: Vec<_> = examples.iter().map(|(key, _)| *key).collect();
+    assert_eq!(keys, vec!["Error::NotFound", "Error::Auth"]);
+    assert_eq!(examples[0].1.status, Status::NotFound);
+    assert_eq!(examples[1].1.status, Status::Forbidden);
+}
@@ -0,0 +1,119 @@
+use rocket::{
+    get,
+    http::{ContentType, Status},
+    local::asynchronous::Client,
+    routes,
+};
+use rocket_simple_responder::{field_violations, FieldViolation, SimpleResponder};
+use serde_json::json;
+use thiserror::Error;
+use validator::{ValidationError, ValidationErrors};
+
+#[derive(Debug, Error, SimpleResponder)]
+#[response(code = 400)]
+enum Error {
+    #[error("invalid input")]
+    Invalid(#[response(validation)] ValidationErrors),
+    #[error("invalid query")]
+    #[response(code = 400)]
+    InvalidQuery(#[response(validation)] Box<ValidationErrors>),
+}
+
+fn errors() -> ValidationErrors {
+    let mut address = ValidationErrors::new();
+    let mut error = ValidationError::new("length");
+    error.message = Some("too long".into());
+    address.add("city", error);
+    let mut errors = ValidationErrors::new();
+    errors.add("email", ValidationError::new("email"));
+    ValidationErrors::merge(Err(errors), "address", Err(address)).unwrap_err()
+}
+
+fn item_errors() -> ValidationErrors {
+    let mut errors = ValidationErrors::new();
+    errors.add("name", ValidationError::new("required"));
+    errors
+}
+
+#[get("/")]
+fn case1_route() -> Error {
+    Error::Invalid(errors())
+}
+
+#[tokio::test]
+async fn case1() {
+    let rocket = rocket::ignite().mount("/", routes![case1_route]);
+    let client = Client::untracked(rocket)
+        .await
+        .expect("valid rocket instance");
+    let response = client.get("/").dispatch().await;
+    assert_eq!(response.status(), Status::UnprocessableEntity);
+    assert_eq!(response.content_type(), Some(ContentType::JSON));
+
+    let body: serde_json::Value =
+        serde_json::from_str(&response.into_string().await.unwrap()).unwrap();
+    assert_eq!(
+        body,
+        json!({
+            "message": "invalid input",
+            "errors": [
+                { "path": "address.city", "code": "length", "message": "too long" },
+                { "path": "email", "code": "email" },
+            ],
+        })
+    );
+}
+
+#[get("/")]
+fn case2_route() -> Error {
+    Error::InvalidQuery(Box::new(item_errors()))
+}
+
+#[tokio::test]
+async fn case2() {
+    let rocket = rocket::ignite().mount("/", routes![case2_route]);
+    let client = Client::untracked(rocket)
+        .await
+        .expect("valid rocket instance");
+    let response = client.get("/").dispatch().await;
+    assert_eq!(response.status(), Status::BadRequest);
+
+    let body: serde_json::Value =
+        serde_json::from_str(&response.into_string().await.unwrap()).unwrap();
+    assert_eq!(
+        body,
+        json!({
+            "message": "invalid query",
+            "errors": [{ "path": "name", "code": "required" }],
+        })
+    );
+}
+
+#[test]
+fn case3() {
+    let errors = ValidationErrors::merge_all(
+        Ok(()),
+        "items",
+        vec![
+            Ok(()),
+            ValidationErrors::merge(Ok(()), "items", Err(item_errors())),
+            ValidationErrors::merge(Ok(()), "items", Err(item_errors())),
+        ],
+    )
+    .unwrap_err();
+    assert_eq!(
+        field_violations(&errors),
+        vec![
+            FieldViolation {
+                path: "items[1].name".into(),
+                code: "required".into(),
+                message: None,
+            },
+            FieldViolation {
+                path: "items[2].name".into(),
+                code: "required".into(),
+                message: None,
+            },
+        ]
+    );
+}